@@ -10,9 +10,10 @@ use rand::{seq::SliceRandom, thread_rng};
 use rayon::{prelude::ParallelIterator, slice::ParallelSlice};
 use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue};
 
+#[cfg(target_arch = "wasm32")]
 pub use wasm_bindgen_rayon::init_thread_pool;
 
-mod raytracer;
+pub mod raytracer;
 
 const CHUNK_SIZE: usize = 500;
 
@@ -45,7 +46,17 @@ impl Scene {
     }
 
     pub fn render(&self, concurrency: usize) -> RenderContext {
-        let mut scene = self.scene.clone();
+        self.render_frame(0, concurrency)
+    }
+
+    #[wasm_bindgen(js_name = frameCount)]
+    pub fn frame_count(&self) -> u32 {
+        self.scene.frame_count()
+    }
+
+    #[wasm_bindgen(js_name = renderFrame)]
+    pub fn render_frame(&self, frame: u32, concurrency: usize) -> RenderContext {
+        let mut scene = self.scene.at_frame(frame);
         scene.camera.initialize();
 
         let width = self.scene.width;
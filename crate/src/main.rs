@@ -0,0 +1,60 @@
+use std::{env, error::Error, fs, path::Path, process};
+
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
+
+use fermion::raytracer::{trace_ray, Scene};
+
+// NOTE - Renders every frame of a scene natively, writing numbered PPM files to the output
+// directory. The Cargo configuration targets WASM by default, so build it for the host, e.g.
+// `cargo run --release --features cli --target x86_64-unknown-linux-gnu -- scene.json out/`.
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+
+    if args.len() != 3 {
+        eprintln!("Usage: {} <scene.json> <output directory>", args[0]);
+        process::exit(1);
+    }
+
+    if let Err(e) = run(Path::new(&args[1]), Path::new(&args[2])) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn run(input_path: &Path, output_path: &Path) -> Result<(), Box<dyn Error>> {
+    let scene: Scene = serde_json::from_str(&fs::read_to_string(input_path)?)?;
+
+    fs::create_dir_all(output_path)?;
+
+    let frame_count = scene.frame_count();
+
+    for frame in 0..frame_count {
+        let mut frame_scene = scene.at_frame(frame);
+        frame_scene.camera.initialize();
+
+        let data = render(&frame_scene);
+
+        let file_path = output_path.join(format!("frame_{:04}.ppm", frame));
+        fs::write(&file_path, data)?;
+
+        println!("Rendered frame {} of {}", frame + 1, frame_count);
+    }
+
+    Ok(())
+}
+
+fn render(scene: &Scene) -> Vec<u8> {
+    let pixels: Vec<(u8, u8, u8)> = (0..scene.height * scene.width)
+        .into_par_iter()
+        .map(|index| trace_ray(scene, index / scene.width, index % scene.width))
+        .collect();
+
+    let mut data = format!("P6\n{} {}\n255\n", scene.width, scene.height).into_bytes();
+
+    for (r, g, b) in pixels {
+        data.extend_from_slice(&[r, g, b]);
+    }
+
+    data
+}
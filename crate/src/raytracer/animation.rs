@@ -0,0 +1,311 @@
+use std::ops;
+
+use serde::Deserialize;
+
+// NOTE - Values that can be interpolated between keyframes.
+
+pub trait Interpolate:
+    Copy + ops::Add<Output = Self> + ops::Sub<Output = Self> + ops::Mul<f32, Output = Self>
+{
+}
+
+impl<T> Interpolate for T where
+    T: Copy + ops::Add<Output = T> + ops::Sub<Output = T> + ops::Mul<f32, Output = T>
+{
+}
+
+#[derive(Copy, Clone, Debug, Default, Deserialize)]
+pub enum Interpolation {
+    #[default]
+    Linear,
+    CatmullRom,
+    Bezier,
+}
+
+// NOTE - Bezier handles are offsets from the keyframe value. They only shape the value, while
+// time always advances linearly between keyframes.
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Keyframe<T> {
+    pub time: f32,
+    pub value: T,
+    pub handle_in: Option<T>,
+    pub handle_out: Option<T>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Track<T> {
+    #[serde(default)]
+    pub interpolation: Interpolation,
+    pub keyframes: Vec<Keyframe<T>>,
+}
+
+impl<T: Interpolate> Track<T> {
+    pub fn sample(&self, time: f32) -> Option<T> {
+        let keyframes = &self.keyframes;
+
+        let first = keyframes.first()?;
+        let last = keyframes.last()?;
+
+        // NOTE - Hold the first and last values outside of the track.
+
+        if time <= first.time {
+            return Some(first.value);
+        }
+
+        if time >= last.time {
+            return Some(last.value);
+        }
+
+        let index = keyframes
+            .windows(2)
+            .position(|pair| time < pair[1].time)
+            .unwrap_or(keyframes.len() - 2);
+
+        let start = &keyframes[index];
+        let finish = &keyframes[index + 1];
+
+        let duration = finish.time - start.time;
+
+        let u = if duration > 0. {
+            (time - start.time) / duration
+        } else {
+            0.
+        };
+
+        let p1 = start.value;
+        let p2 = finish.value;
+
+        let value = match self.interpolation {
+            Interpolation::Linear => p1 + (p2 - p1) * u,
+            Interpolation::CatmullRom => {
+                // NOTE - Uniform Catmull-Rom spline, repeating the end keyframes where there is
+                // no neighbour.
+
+                let p0 = match index.checked_sub(1) {
+                    Some(previous) => keyframes[previous].value,
+                    None => p1,
+                };
+
+                let p3 = match keyframes.get(index + 2) {
+                    Some(next) => next.value,
+                    None => p2,
+                };
+
+                (p1 * 2.
+                    + (p2 - p0) * u
+                    + (p0 * 2. - p1 * 5. + p2 * 4. - p3) * (u * u)
+                    + (p1 * 3. - p0 - p2 * 3. + p3) * (u * u * u))
+                    * 0.5
+            }
+            Interpolation::Bezier => {
+                let c1 = match start.handle_out {
+                    Some(handle) => p1 + handle,
+                    None => p1,
+                };
+
+                let c2 = match finish.handle_in {
+                    Some(handle) => p2 + handle,
+                    None => p2,
+                };
+
+                let v = 1. - u;
+
+                p1 * (v * v * v) + c1 * (3. * v * v * u) + c2 * (3. * v * u * u) + p2 * (u * u * u)
+            }
+        };
+
+        Some(value)
+    }
+}
+
+// NOTE - Either a fixed value or a keyframe track, so existing scenes keep working.
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum Animated<T> {
+    Constant(T),
+    Track(Track<T>),
+}
+
+impl<T: Interpolate + Default> Animated<T> {
+    pub fn at(&self, time: f32) -> T {
+        match self {
+            Animated::Constant(value) => *value,
+            Animated::Track(track) => track.sample(time).unwrap_or_default(),
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        matches!(self, Animated::Constant(_))
+    }
+}
+
+impl<T: Default> Default for Animated<T> {
+    fn default() -> Self {
+        Animated::Constant(T::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keyframe(time: f32, value: f32) -> Keyframe<f32> {
+        Keyframe {
+            time,
+            value,
+            handle_in: None,
+            handle_out: None,
+        }
+    }
+
+    fn track(interpolation: Interpolation, keyframes: Vec<Keyframe<f32>>) -> Track<f32> {
+        Track {
+            interpolation,
+            keyframes,
+        }
+    }
+
+    fn assert_near(actual: Option<f32>, expected: f32) {
+        let actual = actual.unwrap();
+
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "expected {} but was {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn empty_track_has_no_value() {
+        assert!(track(Interpolation::Linear, vec![]).sample(0.).is_none());
+    }
+
+    #[test]
+    fn single_keyframe_is_held() {
+        let track = track(Interpolation::CatmullRom, vec![keyframe(1., 3.)]);
+
+        assert_near(track.sample(0.), 3.);
+        assert_near(track.sample(1.), 3.);
+        assert_near(track.sample(2.), 3.);
+    }
+
+    #[test]
+    fn values_outside_track_are_clamped() {
+        for interpolation in [
+            Interpolation::Linear,
+            Interpolation::CatmullRom,
+            Interpolation::Bezier,
+        ] {
+            let track = track(interpolation, vec![keyframe(1., 2.), keyframe(3., 6.)]);
+
+            assert_near(track.sample(-10.), 2.);
+            assert_near(track.sample(10.), 6.);
+        }
+    }
+
+    #[test]
+    fn keyframes_are_interpolated_exactly() {
+        for interpolation in [
+            Interpolation::Linear,
+            Interpolation::CatmullRom,
+            Interpolation::Bezier,
+        ] {
+            let track = track(
+                interpolation,
+                vec![keyframe(0., 1.), keyframe(1., 4.), keyframe(3., -2.)],
+            );
+
+            assert_near(track.sample(0.), 1.);
+            assert_near(track.sample(1.), 4.);
+            assert_near(track.sample(3.), -2.);
+        }
+    }
+
+    #[test]
+    fn linear_interpolates_within_segment() {
+        let track = track(
+            Interpolation::Linear,
+            vec![keyframe(0., 0.), keyframe(2., 4.), keyframe(4., 0.)],
+        );
+
+        assert_near(track.sample(0.5), 1.);
+        assert_near(track.sample(3.), 2.);
+    }
+
+    #[test]
+    fn catmull_rom_reproduces_evenly_spaced_lines() {
+        let track = track(
+            Interpolation::CatmullRom,
+            vec![
+                keyframe(0., 0.),
+                keyframe(1., 1.),
+                keyframe(2., 2.),
+                keyframe(3., 3.),
+            ],
+        );
+
+        assert_near(track.sample(1.25), 1.25);
+        assert_near(track.sample(1.5), 1.5);
+    }
+
+    #[test]
+    fn bezier_without_handles_is_linear_at_midpoint() {
+        let track = track(
+            Interpolation::Bezier,
+            vec![keyframe(0., 0.), keyframe(1., 1.)],
+        );
+
+        assert_near(track.sample(0.5), 0.5);
+    }
+
+    #[test]
+    fn bezier_handles_shape_the_curve() {
+        let track = track(
+            Interpolation::Bezier,
+            vec![
+                Keyframe {
+                    handle_out: Some(1.),
+                    ..keyframe(0., 0.)
+                },
+                Keyframe {
+                    handle_in: Some(0.),
+                    ..keyframe(1., 1.)
+                },
+            ],
+        );
+
+        // NOTE - Control points 0, 1, 1, 1 evaluated at the middle of the segment.
+
+        assert_near(track.sample(0.5), 0.875);
+        assert_near(track.sample(1.), 1.);
+    }
+
+    #[test]
+    fn coincident_keyframes_jump_without_nan() {
+        let track = track(
+            Interpolation::CatmullRom,
+            vec![
+                keyframe(0., 0.),
+                keyframe(1., 1.),
+                keyframe(1., 5.),
+                keyframe(2., 6.),
+            ],
+        );
+
+        assert_near(track.sample(1.), 5.);
+        assert!(track.sample(0.999).unwrap().is_finite());
+        assert!(track.sample(1.001).unwrap().is_finite());
+    }
+
+    #[test]
+    fn constant_ignores_time() {
+        let animated = Animated::Constant(2.);
+
+        assert!(animated.is_constant());
+        assert_eq!(animated.at(-1.), 2.);
+        assert_eq!(animated.at(100.), 2.);
+    }
+}
@@ -0,0 +1,232 @@
+use std::{convert::TryFrom, f32::consts::PI, sync::Arc};
+
+use serde::Deserialize;
+
+use super::v3::V3;
+
+const MAXIMUM_ATTEMPTS: u32 = 64;
+
+// NOTE - The shape of the lens aperture, which determines the shape of defocused highlights.
+// Samples lie within the unit disk in the lens plane.
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum ApertureShape {
+    #[default]
+    Circle,
+    Polygon {
+        blades: u32,
+        rotation: f32,
+    },
+    Mask(ApertureMask),
+}
+
+impl ApertureShape {
+    pub fn sample(&self) -> V3 {
+        match self {
+            ApertureShape::Circle => V3::random_in_disk(1.),
+            ApertureShape::Polygon { blades, rotation } => sample_polygon(*blades, *rotation),
+            ApertureShape::Mask(mask) => mask.sample(),
+        }
+    }
+
+    pub fn sample_vignetted(&self, offset_x: f32, offset_y: f32) -> V3 {
+        // NOTE - Cat's eye vignetting. Off-axis, the lens barrel clips the aperture, which is
+        // modelled as only keeping samples that also fall within a shifted unit disk. The shift
+        // is limited to the radius of the disk, so the center of the aperture is never clipped.
+
+        let offset_length = (offset_x * offset_x + offset_y * offset_y).sqrt();
+        let offset_scale = if offset_length > 1. {
+            1. / offset_length
+        } else {
+            1.
+        };
+
+        let (offset_x, offset_y) = (offset_x * offset_scale, offset_y * offset_scale);
+
+        for _ in 0..MAXIMUM_ATTEMPTS {
+            let sample = self.sample();
+
+            let dx = sample.x - offset_x;
+            let dy = sample.y - offset_y;
+
+            if dx * dx + dy * dy <= 1. {
+                return sample;
+            }
+        }
+
+        // NOTE - Only reached for masks that are almost empty within the shifted disk. An
+        // unclipped sample at least stays within the aperture.
+
+        self.sample()
+    }
+}
+
+// NOTE - A greyscale mask covering the square around the unit disk, with its first row at the
+// top. Texels are importance sampled from a cumulative distribution built when it is loaded.
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "ApertureMaskSource")]
+pub struct ApertureMask {
+    pub width: usize,
+    pub height: usize,
+    cdf: Arc<Vec<f32>>,
+}
+
+#[derive(Deserialize)]
+struct ApertureMaskSource {
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl TryFrom<ApertureMaskSource> for ApertureMask {
+    type Error = String;
+
+    fn try_from(source: ApertureMaskSource) -> Result<Self, Self::Error> {
+        let count = source
+            .width
+            .checked_mul(source.height)
+            .ok_or_else(|| "Aperture mask dimensions are too large".to_string())?;
+
+        if count == 0 {
+            return Err("Aperture mask dimensions must be non-zero".to_string());
+        }
+
+        if source.values.len() != count {
+            return Err(format!(
+                "Aperture mask expected {} values but found {}",
+                count,
+                source.values.len()
+            ));
+        }
+
+        if let Some(value) = source
+            .values
+            .iter()
+            .find(|value| !(**value >= 0. && value.is_finite()))
+        {
+            return Err(format!(
+                "Aperture mask values must be non-negative, but found {}",
+                value
+            ));
+        }
+
+        let mut total = 0.;
+
+        let mut cdf: Vec<f32> = source
+            .values
+            .iter()
+            .map(|value| {
+                total += value;
+                total
+            })
+            .collect();
+
+        if total <= 0. {
+            return Err("Aperture mask must have at least one non-zero value".to_string());
+        }
+
+        for value in cdf.iter_mut() {
+            *value /= total;
+        }
+
+        Ok(ApertureMask {
+            width: source.width,
+            height: source.height,
+            cdf: Arc::new(cdf),
+        })
+    }
+}
+
+impl ApertureMask {
+    fn sample(&self) -> V3 {
+        // NOTE - Texels with no weight don't raise the distribution, so they are never chosen.
+
+        let target = rand::random::<f32>();
+        let index = self
+            .cdf
+            .partition_point(|value| *value <= target)
+            .min(self.cdf.len() - 1);
+
+        let column = (index % self.width) as f32 + rand::random::<f32>();
+        let row = (index / self.width) as f32 + rand::random::<f32>();
+
+        V3 {
+            x: 2. * column / self.width as f32 - 1.,
+            y: 1. - 2. * row / self.height as f32,
+            z: 0.,
+        }
+    }
+}
+
+fn sample_polygon(blades: u32, rotation: f32) -> V3 {
+    // NOTE - Choose one of the triangles fanning out from the center, then sample it uniformly.
+
+    let blades = blades.max(3);
+    let sector = 2. * PI / blades as f32;
+
+    let index = ((rand::random::<f32>() * blades as f32) as u32).min(blades - 1);
+    let angle = rotation.to_radians() + index as f32 * sector;
+
+    let (mut a, mut b) = (rand::random::<f32>(), rand::random::<f32>());
+
+    if a + b > 1. {
+        a = 1. - a;
+        b = 1. - b;
+    }
+
+    V3 {
+        x: a * angle.cos() + b * (angle + sector).cos(),
+        y: a * angle.sin() + b * (angle + sector).sin(),
+        z: 0.,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask(width: usize, height: usize, values: Vec<f32>) -> Result<ApertureMask, String> {
+        ApertureMask::try_from(ApertureMaskSource {
+            width,
+            height,
+            values,
+        })
+    }
+
+    #[test]
+    fn mask_rejects_invalid_values() {
+        assert!(mask(0, 1, vec![]).is_err());
+        assert!(mask(2, 2, vec![1., 1., 1.]).is_err());
+        assert!(mask(usize::MAX, 2, vec![1.]).is_err());
+        assert!(mask(2, 1, vec![1., -1.]).is_err());
+        assert!(mask(2, 1, vec![1., f32::NAN]).is_err());
+        assert!(mask(2, 1, vec![0., 0.]).is_err());
+    }
+
+    #[test]
+    fn mask_samples_only_open_texels() {
+        // NOTE - Only the bottom right quadrant is open.
+
+        let mask = mask(2, 2, vec![0., 0., 0., 1.]).unwrap();
+
+        for _ in 0..1000 {
+            let sample = mask.sample();
+
+            assert!((0. ..=1.).contains(&sample.x), "{:?}", sample);
+            assert!((-1. ..=0.).contains(&sample.y), "{:?}", sample);
+        }
+    }
+
+    #[test]
+    fn vignetted_samples_stay_within_aperture() {
+        let shape = ApertureShape::Circle;
+
+        for _ in 0..1000 {
+            let sample = shape.sample_vignetted(10., -10.);
+
+            assert!(sample.x * sample.x + sample.y * sample.y <= 1. + 1e-5);
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+
+use crate::raytracer::{color::Color, ray::Ray};
+
+use super::Background;
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct GradientBackground {
+    pub color_bottom: Color,
+    pub color_top: Color,
+}
+
+impl Background for GradientBackground {
+    fn value(&self, ray: &Ray) -> Color {
+        // NOTE - Linear interpolation based on the vertical component of the ray direction.
+
+        let coefficient = 0.5 * (ray.direction.unit().y + 1.);
+
+        Color {
+            r: (1. - coefficient) * self.color_bottom.r + coefficient * self.color_top.r,
+            g: (1. - coefficient) * self.color_bottom.g + coefficient * self.color_top.g,
+            b: (1. - coefficient) * self.color_bottom.b + coefficient * self.color_top.b,
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+use self::{gradient::GradientBackground, solid::SolidBackground, texture::TextureBackground};
+
+use super::{color::Color, ray::Ray};
+
+mod gradient;
+mod solid;
+mod texture;
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum BackgroundKind {
+    Gradient(GradientBackground),
+    Solid(SolidBackground),
+    Texture(TextureBackground),
+}
+
+pub trait Background {
+    fn value(&self, ray: &Ray) -> Color;
+}
+
+impl Background for BackgroundKind {
+    fn value(&self, ray: &Ray) -> Color {
+        match self {
+            BackgroundKind::Gradient(x) => x.value(ray),
+            BackgroundKind::Solid(x) => x.value(ray),
+            BackgroundKind::Texture(x) => x.value(ray),
+        }
+    }
+}
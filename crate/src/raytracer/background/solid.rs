@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use crate::raytracer::{color::Color, ray::Ray};
+
+use super::Background;
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct SolidBackground {
+    pub color: Color,
+}
+
+impl Background for SolidBackground {
+    fn value(&self, _ray: &Ray) -> Color {
+        self.color
+    }
+}
@@ -0,0 +1,24 @@
+use serde::Deserialize;
+
+use crate::raytracer::{
+    color::Color,
+    ray::Ray,
+    texture::{Texture, TextureKind},
+    utils::get_spherical_uv,
+};
+
+use super::Background;
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct TextureBackground {
+    pub texture: TextureKind,
+}
+
+impl Background for TextureBackground {
+    fn value(&self, ray: &Ray) -> Color {
+        let direction = ray.direction.unit();
+        let (u, v) = get_spherical_uv(&direction);
+
+        self.texture.value(u, v, direction)
+    }
+}
@@ -1,84 +1,539 @@
+use std::{convert::TryFrom, f32::consts::PI};
+
 use serde::Deserialize;
 
 use super::{
+    animation::Animated,
+    aperture::ApertureShape,
     ray::Ray,
-    utils::random_in_range,
+    shutter::ShutterCurve,
     v3::{P3, V3},
 };
 
+#[derive(Copy, Clone, Debug, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum Projection {
+    #[default]
+    Perspective,
+    Orthographic {
+        view_width: f32,
+    },
+    Equirectangular,
+    Fisheye {
+        mapping: FisheyeMapping,
+        field_of_view: f32,
+    },
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum FisheyeMapping {
+    Equidistant,
+    Equisolid,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub enum StereoLayout {
+    SideBySide,
+    TopBottom,
+}
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+pub struct Stereo {
+    pub layout: StereoLayout,
+    pub interpupillary_distance: f32,
+    pub convergence_distance: f32,
+}
+
+// NOTE - Brown-Conrady coefficients, with k for radial and p for tangential distortion. They
+// map undistorted to distorted image coordinates, measured in half image heights from the center.
+
+#[derive(Copy, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LensDistortion {
+    pub k1: f32,
+    pub k2: f32,
+    pub k3: f32,
+    pub p1: f32,
+    pub p2: f32,
+}
+
+impl LensDistortion {
+    pub fn distort(&self, x: f32, y: f32) -> (f32, f32) {
+        let r2 = x * x + y * y;
+        let radial = 1. + r2 * (self.k1 + r2 * (self.k2 + r2 * self.k3));
+
+        (
+            x * radial + 2. * self.p1 * x * y + self.p2 * (r2 + 2. * x * x),
+            y * radial + self.p1 * (r2 + 2. * y * y) + 2. * self.p2 * x * y,
+        )
+    }
+
+    pub fn undistort(&self, x: f32, y: f32) -> (f32, f32) {
+        // NOTE - There is no closed form inverse, so refine a guess by fixed point iteration.
+
+        let (mut ux, mut uy) = (x, y);
+
+        for _ in 0..8 {
+            let (dx, dy) = self.distort(ux, uy);
+
+            ux += x - dx;
+            uy += y - dy;
+        }
+
+        (ux, uy)
+    }
+}
+
+// NOTE - Shifts move the image plane by fractions of its width and height, keeping parallel
+// lines parallel. Tilts in degrees rotate the plane of focus about the camera's horizontal and
+// vertical axes, following the Scheimpflug principle.
+
+#[derive(Copy, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TiltShift {
+    pub shift_horizontal: f32,
+    pub shift_vertical: f32,
+    pub tilt_horizontal: f32,
+    pub tilt_vertical: f32,
+}
+
+// NOTE - Camera settings in photographic units, with the scene measured in metres. The focal
+// length follows from the field of view and the sensor height, which defaults to full frame.
+
 #[derive(Copy, Clone, Deserialize)]
+#[serde(try_from = "PhysicalCameraSource")]
+pub struct PhysicalCamera {
+    pub f_number: f32,
+    pub shutter_speed: f32,
+    pub iso: f32,
+    pub sensor_height: f32,
+}
+
+#[derive(Deserialize)]
+struct PhysicalCameraSource {
+    f_number: f32,
+    shutter_speed: f32,
+    iso: f32,
+    #[serde(default = "PhysicalCamera::default_sensor_height")]
+    sensor_height: f32,
+}
+
+impl TryFrom<PhysicalCameraSource> for PhysicalCamera {
+    type Error = String;
+
+    fn try_from(source: PhysicalCameraSource) -> Result<Self, Self::Error> {
+        for (name, value) in [
+            ("f_number", source.f_number),
+            ("shutter_speed", source.shutter_speed),
+            ("iso", source.iso),
+            ("sensor_height", source.sensor_height),
+        ] {
+            if !(value > 0. && value.is_finite()) {
+                return Err(format!(
+                    "Physical camera {} must be positive, but was {}",
+                    name, value
+                ));
+            }
+        }
+
+        Ok(PhysicalCamera {
+            f_number: source.f_number,
+            shutter_speed: source.shutter_speed,
+            iso: source.iso,
+            sensor_height: source.sensor_height,
+        })
+    }
+}
+
+impl PhysicalCamera {
+    fn default_sensor_height() -> f32 {
+        0.024
+    }
+
+    pub fn focal_length(&self, vertical_field_of_view: f32) -> f32 {
+        self.sensor_height / (2. * (vertical_field_of_view.to_radians() / 2.).tan())
+    }
+
+    pub fn exposure(&self) -> f32 {
+        // NOTE - Saturation based sensitivity, where a luminance of 1 at EV100 0 is scaled so
+        // that it just reaches white.
+
+        let ev100 = (self.f_number * self.f_number / self.shutter_speed * 100. / self.iso).log2();
+
+        1. / (1.2 * 2f32.powf(ev100))
+    }
+}
+
+// NOTE - The camera's orientation and image plane at a moment in time.
+
+#[derive(Copy, Clone, Default)]
+struct View {
+    look_from: P3,
+    focus_distance: f32,
+    u: V3,
+    v: V3,
+    w: V3,
+    horizontal: V3,
+    vertical: V3,
+    lower_left_corner: P3,
+    focus_normal: V3,
+    lens_radius: f32,
+}
+
+impl View {
+    fn camera_to_world(&self, right: f32, up: f32, forward: f32) -> V3 {
+        right * self.u + up * self.v - forward * self.w
+    }
+}
+
+#[derive(Clone, Deserialize)]
 pub struct Camera {
-    pub look_from: P3,
-    pub look_at: P3,
+    pub look_from: Animated<P3>,
+    pub look_at: Animated<P3>,
     pub view_up: V3,
-    pub vertical_field_of_view: f32,
+    pub vertical_field_of_view: Animated<f32>,
     pub aspect_ratio: f32,
     pub aperture: f32,
-    pub focus_distance: f32,
+    pub focus_distance: Animated<f32>,
     pub time_start: f32,
     pub time_finish: f32,
+    #[serde(default)]
+    pub projection: Projection,
+    #[serde(default)]
+    pub stereo: Option<Stereo>,
+    #[serde(default)]
+    pub aperture_shape: ApertureShape,
+    #[serde(default)]
+    pub cat_eye: f32,
+    #[serde(default)]
+    pub physical: Option<PhysicalCamera>,
+    #[serde(default)]
+    pub tilt_shift: Option<TiltShift>,
+    #[serde(default)]
+    pub distortion: Option<LensDistortion>,
+    #[serde(default)]
+    pub chromatic_aberration: f32,
+    #[serde(default)]
+    pub shutter_curve: ShutterCurve,
+    #[serde(default)]
+    pub readout_duration: f32,
 
     #[serde(skip)]
-    view_width: f32,
-    #[serde(skip)]
-    view_height: f32,
-    #[serde(skip)]
-    u: V3,
-    #[serde(skip)]
-    v: V3,
-    #[serde(skip)]
-    w: V3,
-    #[serde(skip)]
-    horizontal: V3,
+    view: View,
     #[serde(skip)]
-    vertical: V3,
-    #[serde(skip)]
-    lower_left_corner: P3,
-    #[serde(skip)]
-    lens_radius: f32,
+    exposure: f32,
 }
 
 impl Camera {
     pub fn initialize(&mut self) {
-        let theta = self.vertical_field_of_view.to_radians();
-        let h = (theta / 2.).tan();
+        self.exposure = 1.;
 
-        let view_height = 2. * h;
-        let view_width = view_height * self.aspect_ratio;
+        // NOTE - A physical camera overrides the aperture and shutter interval.
+
+        if let Some(physical) = self.physical {
+            self.time_finish = self.time_start + physical.shutter_speed;
+            self.exposure = physical.exposure();
+        }
 
-        let w = (self.look_from - self.look_at).unit();
+        self.view = self.view_at(self.time_start);
+    }
+
+    fn is_animated(&self) -> bool {
+        !(self.look_from.is_constant()
+            && self.look_at.is_constant()
+            && self.vertical_field_of_view.is_constant()
+            && self.focus_distance.is_constant())
+    }
+
+    fn view_at(&self, time: f32) -> View {
+        let look_from = self.look_from.at(time);
+        let look_at = self.look_at.at(time);
+        let vertical_field_of_view = self.vertical_field_of_view.at(time);
+        let focus_distance = self.focus_distance.at(time);
+
+        let (view_width, view_height) = match self.projection {
+            Projection::Perspective | Projection::Equirectangular | Projection::Fisheye { .. } => {
+                let theta = vertical_field_of_view.to_radians();
+                let h = (theta / 2.).tan();
+
+                let view_height = 2. * h;
+
+                (view_height * self.eye_aspect_ratio(), view_height)
+            }
+            Projection::Orthographic { view_width } => {
+                // NOTE - The view is measured in world units rather than at unit distance, so
+                // undo the scaling by the focus distance below.
+
+                (
+                    view_width / focus_distance,
+                    view_width / self.eye_aspect_ratio() / focus_distance,
+                )
+            }
+        };
+
+        let w = (look_from - look_at).unit();
         let u = V3::cross(&self.view_up, &w).unit();
         let v = V3::cross(&w, &u);
 
-        let horizontal = (self.focus_distance * view_width) * u;
-        let vertical = (self.focus_distance * view_height) * v;
+        let horizontal = (focus_distance * view_width) * u;
+        let vertical = (focus_distance * view_height) * v;
+
+        let tilt_shift = self.tilt_shift.unwrap_or_default();
+
+        let lower_left_corner = look_from - horizontal / 2. - vertical / 2.
+            + tilt_shift.shift_horizontal * horizontal
+            + tilt_shift.shift_vertical * vertical
+            - focus_distance * w;
+
+        let focus_normal = (-w
+            + tilt_shift.tilt_horizontal.to_radians().tan() * v
+            + tilt_shift.tilt_vertical.to_radians().tan() * u)
+            .unit();
+
+        let lens_radius = match self.physical {
+            Some(physical) => {
+                physical.focal_length(vertical_field_of_view) / physical.f_number / 2.
+            }
+            None => self.aperture / 2.,
+        };
+
+        View {
+            look_from,
+            focus_distance,
+            u,
+            v,
+            w,
+            horizontal,
+            vertical,
+            lower_left_corner,
+            focus_normal,
+            lens_radius,
+        }
+    }
+
+    pub fn exposure(&self) -> f32 {
+        self.exposure
+    }
+
+    pub fn make_ray(&self, s: f32, t: f32, wavelength: Option<f32>) -> Option<Ray> {
+        // NOTE - With a rolling shutter, rows are exposed one after another from the top of the
+        // image, each starting later by a fraction of the readout duration.
+
+        let time = self.time_start
+            + self.shutter_curve.sample() * (self.time_finish - self.time_start)
+            + (1. - t) * self.readout_duration;
+
+        let (eye_offset, s, t) = self.split_stereo(s, t);
+        let (s, t) = self.apply_lens_aberrations(s, t, wavelength);
+
+        let view = if self.is_animated() {
+            self.view_at(time)
+        } else {
+            self.view
+        };
+
+        let (position, direction) = match self.projection {
+            Projection::Perspective | Projection::Orthographic { .. } => {
+                self.make_planar_ray(&view, s, t, eye_offset)
+            }
+            Projection::Equirectangular => self.make_equirectangular_ray(&view, s, t, eye_offset),
+            Projection::Fisheye {
+                mapping,
+                field_of_view,
+            } => self.make_fisheye_ray(&view, s, t, eye_offset, mapping, field_of_view)?,
+        };
+
+        Some(Ray {
+            position,
+            direction,
+            time,
+            wavelength: None,
+        })
+    }
+
+    fn apply_lens_aberrations(&self, s: f32, t: f32, wavelength: Option<f32>) -> (f32, f32) {
+        // NOTE - Find where the distorted image point came from. Lateral chromatic aberration
+        // magnifies each wavelength differently, with the amount being the relative difference
+        // between 650nm and 450nm.
+
+        let magnification = match wavelength {
+            Some(wavelength) => 1. + self.chromatic_aberration * (wavelength - 550.) / 200.,
+            None => 1.,
+        };
+
+        if self.distortion.is_none() && magnification == 1. {
+            return (s, t);
+        }
+
+        let x = (2. * s - 1.) * self.eye_aspect_ratio();
+        let y = 2. * t - 1.;
+
+        let (x, y) = match self.distortion {
+            Some(distortion) => distortion.undistort(x, y),
+            None => (x, y),
+        };
+
+        (
+            (x / magnification / self.eye_aspect_ratio() + 1.) / 2.,
+            (y / magnification + 1.) / 2.,
+        )
+    }
+
+    fn eye_aspect_ratio(&self) -> f32 {
+        // NOTE - With stereo, each eye only sees half of the image, so its view has its own
+        // aspect ratio rather than being squeezed to fit.
+
+        match self.stereo.map(|stereo| stereo.layout) {
+            Some(StereoLayout::SideBySide) => self.aspect_ratio / 2.,
+            Some(StereoLayout::TopBottom) => self.aspect_ratio * 2.,
+            None => self.aspect_ratio,
+        }
+    }
+
+    fn split_stereo(&self, s: f32, t: f32) -> (f32, f32, f32) {
+        // NOTE - Splits the image into a view for each eye, returning the signed offset of the
+        // eye along the camera's horizontal axis and the coordinates within that eye's view.
+        // The left eye is on the left or top.
+
+        let stereo = match self.stereo {
+            Some(stereo) => stereo,
+            None => return (0., s, t),
+        };
+
+        let half_distance = stereo.interpupillary_distance / 2.;
+
+        match stereo.layout {
+            StereoLayout::SideBySide if s < 0.5 => (-half_distance, 2. * s, t),
+            StereoLayout::SideBySide => (half_distance, 2. * s - 1., t),
+            StereoLayout::TopBottom if t >= 0.5 => (-half_distance, s, 2. * t - 1.),
+            StereoLayout::TopBottom => (half_distance, s, 2. * t),
+        }
+    }
+
+    fn make_planar_ray(&self, view: &View, s: f32, t: f32, eye_offset: f32) -> (P3, V3) {
+        let focus_point = view.lower_left_corner + s * view.horizontal + t * view.vertical;
+
+        // NOTE - Perspective rays all leave from the eye, while orthographic rays leave from
+        // the image plane and travel parallel to the view direction.
+
+        let center = match self.projection {
+            Projection::Orthographic { .. } => focus_point + view.focus_distance * view.w,
+            _ => view.look_from,
+        };
+
+        // NOTE - Each eye looks through the same window at the convergence distance (an
+        // off-axis frustum), so objects there have zero parallax. The ray is then refocused on
+        // the plane of focus, which may be tilted.
+
+        let eye = center + eye_offset * view.u;
+
+        let convergence_distance = match self.stereo {
+            Some(stereo) => stereo.convergence_distance,
+            None => view.focus_distance,
+        };
+
+        let target = center + (convergence_distance / view.focus_distance) * (focus_point - center);
+        let eye_direction = target - eye;
+
+        let focus_center = view.look_from - view.focus_distance * view.w;
+
+        // NOTE - Steep tilts leave some rays parallel to the plane of focus, or meeting it
+        // behind the eye. Those are focused on the untilted plane instead.
+
+        let tilted_scale = V3::dot(&(focus_center - eye), &view.focus_normal)
+            / V3::dot(&eye_direction, &view.focus_normal);
+
+        let focus_scale = if tilted_scale > 0. && tilted_scale.is_finite() {
+            tilted_scale
+        } else {
+            V3::dot(&(focus_center - eye), &-view.w) / V3::dot(&eye_direction, &-view.w)
+        };
+
+        let focus_point = eye + focus_scale * eye_direction;
+
+        // NOTE - Introduce defocus blur. Vignetting shifts towards the center of the image.
 
-        let lower_left_corner =
-            self.look_from - horizontal / 2. - vertical / 2. - self.focus_distance * w;
+        let defocus_weights = view.lens_radius
+            * if self.cat_eye > 0. {
+                self.aperture_shape
+                    .sample_vignetted(self.cat_eye * (1. - 2. * s), self.cat_eye * (1. - 2. * t))
+            } else {
+                self.aperture_shape.sample()
+            };
+        let defocus_offset = defocus_weights.x * view.u + defocus_weights.y * view.v;
 
-        self.view_height = view_height;
-        self.view_width = view_width;
-        self.u = u;
-        self.v = v;
-        self.w = w;
-        self.horizontal = horizontal;
-        self.vertical = vertical;
-        self.lower_left_corner = lower_left_corner;
-        self.lens_radius = self.aperture / 2.;
+        (eye + defocus_offset, focus_point - eye - defocus_offset)
     }
 
-    pub fn make_ray(&self, s: f32, t: f32) -> Ray {
-        // NOTE - Introduce defocus blur.
+    fn make_equirectangular_ray(&self, view: &View, s: f32, t: f32, eye_offset: f32) -> (P3, V3) {
+        // NOTE - The image spans 360 degrees of longitude and 180 degrees of latitude, centered
+        // on the view direction.
+
+        let phi = (s - 0.5) * 2. * PI;
+        let theta = (t - 0.5) * PI;
+
+        let direction = view.camera_to_world(
+            theta.cos() * phi.sin(),
+            theta.sin(),
+            theta.cos() * phi.cos(),
+        );
+
+        // NOTE - Omni-directional stereo. The eyes sit on a circle and each ray leaves from the
+        // point where it is tangent to it, so every longitude has the correct parallax.
+
+        let eye = view.look_from + eye_offset * view.camera_to_world(phi.cos(), 0., -phi.sin());
+
+        (eye, self.converge(view, eye, direction))
+    }
+
+    fn make_fisheye_ray(
+        &self,
+        view: &View,
+        s: f32,
+        t: f32,
+        eye_offset: f32,
+        mapping: FisheyeMapping,
+        field_of_view: f32,
+    ) -> Option<(P3, V3)> {
+        // NOTE - The image circle fills the height of the image. Samples outside of it produce
+        // no ray.
+
+        let x = (2. * s - 1.) * self.eye_aspect_ratio();
+        let y = 2. * t - 1.;
+
+        let r = (x * x + y * y).sqrt();
+
+        if r > 1. {
+            return None;
+        }
+
+        let theta_max = field_of_view.to_radians() / 2.;
+
+        let theta = match mapping {
+            FisheyeMapping::Equidistant => r * theta_max,
+            FisheyeMapping::Equisolid => 2. * (r * (theta_max / 2.).sin()).clamp(-1., 1.).asin(),
+        };
+
+        let phi = y.atan2(x);
+
+        let direction = view.camera_to_world(
+            theta.sin() * phi.cos(),
+            theta.sin() * phi.sin(),
+            theta.cos(),
+        );
+
+        let eye = view.look_from + eye_offset * view.u;
+
+        Some((eye, self.converge(view, eye, direction)))
+    }
 
-        let defocus_weights = self.lens_radius * V3::random_in_disk(1.);
-        let defocus_offset = defocus_weights.x * self.u + defocus_weights.y * self.v;
+    fn converge(&self, view: &View, eye: P3, direction: V3) -> V3 {
+        // NOTE - Aim a ray from an offset eye at the point the central ray would reach at the
+        // convergence distance.
 
-        Ray {
-            position: self.look_from + defocus_offset,
-            direction: self.lower_left_corner + s * self.horizontal + t * self.vertical
-                - self.look_from
-                - defocus_offset,
-            time: random_in_range(self.time_start, self.time_finish),
+        match self.stereo {
+            Some(stereo) => view.look_from + stereo.convergence_distance * direction.unit() - eye,
+            None => direction,
         }
     }
 }
@@ -0,0 +1,46 @@
+use super::v3::V3;
+
+#[derive(Clone, Copy, Debug)]
+pub struct Frame {
+    pub tangent: V3,
+    pub bitangent: V3,
+    pub normal: V3,
+}
+
+impl Frame {
+    pub fn from_normal(normal: V3) -> Frame {
+        // NOTE - Branchless orthonormal basis from Duff et al. (2017).
+
+        let normal = normal.unit();
+
+        let sign = 1_f32.copysign(normal.z);
+        let a = -1. / (sign + normal.z);
+        let b = normal.x * normal.y * a;
+
+        Frame {
+            tangent: V3 {
+                x: 1. + sign * normal.x * normal.x * a,
+                y: sign * b,
+                z: -sign * normal.x,
+            },
+            bitangent: V3 {
+                x: b,
+                y: sign + normal.y * normal.y * a,
+                z: -normal.y,
+            },
+            normal,
+        }
+    }
+
+    pub fn to_local(self, v: &V3) -> V3 {
+        V3 {
+            x: V3::dot(v, &self.tangent),
+            y: V3::dot(v, &self.bitangent),
+            z: V3::dot(v, &self.normal),
+        }
+    }
+
+    pub fn to_world(self, v: &V3) -> V3 {
+        v.x * self.tangent + v.y * self.bitangent + v.z * self.normal
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+use crate::raytracer::{color::Color, object::Hit, ray::Ray};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct Absorption {
+    pub color: Color,
+    pub density: f32,
+}
+
+impl Absorption {
+    pub fn transmittance(&self, ray_in: &Ray, hit: &Hit) -> Color {
+        // NOTE - A ray hitting the back of the surface has travelled through the medium since it
+        // last entered it. The Beer-Lambert law attenuates it so that light is tinted to the
+        // absorption color after travelling a distance of 1 / density.
+
+        if hit.is_front {
+            return Color::gray(1.);
+        }
+
+        let distance = hit.t * ray_in.direction.len();
+        let optical_depth = self.density * distance;
+
+        Color {
+            r: self.color.r.max(0.).powf(optical_depth),
+            g: self.color.g.max(0.).powf(optical_depth),
+            b: self.color.b.max(0.).powf(optical_depth),
+        }
+    }
+}
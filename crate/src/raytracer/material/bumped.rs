@@ -0,0 +1,73 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::raytracer::{
+    object::Hit,
+    ray::Ray,
+    texture::{ScalarTexture, Texture, TextureKind},
+    v3::V3,
+};
+
+use super::{Material, MaterialKind, ScatterResult};
+
+const BUMP_DELTA: f32 = 1e-3;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum NormalPerturbation {
+    BumpMap { texture: ScalarTexture, scale: f32 },
+    NormalMap { texture: TextureKind },
+}
+
+// NOTE - Perturbs the shading normal of another material, either from a tangent-space normal
+// map or from the derivatives of a height map.
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct BumpedMaterial {
+    pub material: Arc<MaterialKind>,
+    pub perturbation: NormalPerturbation,
+}
+
+impl BumpedMaterial {
+    fn shading_normal(&self, hit: &Hit) -> V3 {
+        let normal = hit.normal;
+        let tangent = (hit.tangent - V3::dot(&hit.tangent, &normal) * normal).unit();
+        let bitangent = V3::cross(&normal, &tangent);
+
+        let perturbed_normal = match self.perturbation {
+            NormalPerturbation::BumpMap { texture, scale } => {
+                let height = texture.value(hit.u, hit.v, hit.position);
+                let height_u = texture.value(hit.u + BUMP_DELTA, hit.v, hit.position);
+                let height_v = texture.value(hit.u, hit.v + BUMP_DELTA, hit.position);
+
+                let derivative_u = (height_u - height) / BUMP_DELTA;
+                let derivative_v = (height_v - height) / BUMP_DELTA;
+
+                normal - scale * (derivative_u * tangent + derivative_v * bitangent)
+            }
+            NormalPerturbation::NormalMap { texture } => {
+                let color = texture.value(hit.u, hit.v, hit.position);
+
+                (2. * color.r - 1.) * tangent
+                    + (2. * color.g - 1.) * bitangent
+                    + (2. * color.b - 1.) * normal
+            }
+        };
+
+        if perturbed_normal.is_near_zero() {
+            normal
+        } else {
+            perturbed_normal.unit()
+        }
+    }
+}
+
+impl Material for BumpedMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let mut shading_hit = hit.clone();
+        shading_hit.normal = self.shading_normal(hit);
+
+        self.material.scatter(ray_in, &shading_hit)
+    }
+}
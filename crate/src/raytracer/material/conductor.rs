@@ -0,0 +1,139 @@
+use serde::Deserialize;
+
+use crate::raytracer::{
+    color::Color, frame::Frame, object::Hit, ray::Ray, spectrum::evaluate_per_wavelength, v3::V3,
+};
+
+use super::{
+    microfacet::{fresnel_conductor, masking_weight, roughness_to_alpha, sample_visible_normal},
+    thin_film::{Substrate, ThinFilm},
+    Material, ScatterResult,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum ConductorIor {
+    Aluminum,
+    Copper,
+    Gold,
+    Silver,
+    Custom { eta: Color, k: Color },
+}
+
+impl ConductorIor {
+    pub fn value(&self) -> (Color, Color) {
+        // NOTE - Complex refractive indices sampled at roughly 650nm, 550nm and 450nm.
+
+        match self {
+            ConductorIor::Aluminum => (
+                Color {
+                    r: 1.657,
+                    g: 0.880,
+                    b: 0.521,
+                },
+                Color {
+                    r: 9.224,
+                    g: 6.270,
+                    b: 4.837,
+                },
+            ),
+            ConductorIor::Copper => (
+                Color {
+                    r: 0.200,
+                    g: 0.924,
+                    b: 1.102,
+                },
+                Color {
+                    r: 3.912,
+                    g: 2.452,
+                    b: 2.142,
+                },
+            ),
+            ConductorIor::Gold => (
+                Color {
+                    r: 0.143,
+                    g: 0.374,
+                    b: 1.442,
+                },
+                Color {
+                    r: 3.983,
+                    g: 2.386,
+                    b: 1.603,
+                },
+            ),
+            ConductorIor::Silver => (
+                Color {
+                    r: 0.155,
+                    g: 0.117,
+                    b: 0.138,
+                },
+                Color {
+                    r: 4.828,
+                    g: 3.122,
+                    b: 2.147,
+                },
+            ),
+            ConductorIor::Custom { eta, k } => (*eta, *k),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ConductorMaterial {
+    pub ior: ConductorIor,
+    pub roughness: f32,
+    #[serde(default)]
+    pub thin_film: Option<ThinFilm>,
+}
+
+impl Material for ConductorMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let frame = Frame::from_normal(hit.normal);
+        let alpha = roughness_to_alpha(self.roughness);
+
+        let wo = frame.to_local(&-ray_in.direction.unit());
+
+        if wo.z <= 0. {
+            return None;
+        }
+
+        let m = sample_visible_normal(&wo, alpha);
+        let wi = V3::reflect(-wo, m);
+
+        if wi.z <= 0. {
+            return None;
+        }
+
+        let cos_theta = V3::dot(&wo, &m).max(0.);
+        let (eta, k) = self.ior.value();
+        let masking = masking_weight(&wo, &wi, alpha);
+
+        Some(ScatterResult {
+            ray_out: Ray {
+                position: hit.position,
+                direction: frame.to_world(&wi),
+                time: ray_in.time,
+                wavelength: ray_in.wavelength,
+            },
+            attenuation: match self.thin_film {
+                Some(thin_film) => {
+                    masking
+                        * evaluate_per_wavelength(ray_in.wavelength, |wavelength, sample| {
+                            thin_film.reflectance(
+                                cos_theta,
+                                1.,
+                                Substrate::Conductor(sample(eta), sample(k)),
+                                wavelength,
+                            )
+                        })
+                }
+                None => Color {
+                    r: masking * fresnel_conductor(cos_theta, eta.r, k.r),
+                    g: masking * fresnel_conductor(cos_theta, eta.g, k.g),
+                    b: masking * fresnel_conductor(cos_theta, eta.b, k.b),
+                },
+            },
+            interior_medium: None,
+        })
+    }
+}
@@ -1,20 +1,38 @@
 use serde::Deserialize;
 
-use crate::raytracer::{color::Color, object::Hit, ray::Ray, v3::V3};
+use crate::raytracer::{
+    color::Color, object::Hit, ray::Ray, spectrum::evaluate_per_wavelength, v3::V3,
+};
 
-use super::{Material, ScatterResult};
+use super::{
+    absorption::Absorption,
+    dispersion::Dispersion,
+    thin_film::{Substrate, ThinFilm},
+    Material, ScatterResult,
+};
 
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub struct DialectricMaterial {
     pub refractive_index: f32,
+    #[serde(default)]
+    pub absorption: Option<Absorption>,
+    #[serde(default)]
+    pub dispersion: Option<Dispersion>,
+    #[serde(default)]
+    pub thin_film: Option<ThinFilm>,
 }
 
 impl Material for DialectricMaterial {
     fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let refractive_index = match (self.dispersion, ray_in.wavelength) {
+            (Some(dispersion), Some(wavelength)) => dispersion.refractive_index(wavelength),
+            _ => self.refractive_index,
+        };
+
         let refractive_index_ratio = if hit.is_front {
-            1. / self.refractive_index
+            1. / refractive_index
         } else {
-            self.refractive_index
+            refractive_index
         };
 
         let unit_direction = ray_in.direction.unit();
@@ -24,15 +42,61 @@ impl Material for DialectricMaterial {
 
         let is_total_internal_reflection = refractive_index_ratio * sin_theta > 1.;
 
-        // NOTE - Use Schlick's approximation to calculate reflectance.
+        // NOTE - Use Schlick's approximation to calculate reflectance, unless there is a thin
+        // film coating. The film reflects each wavelength differently, so its average is used to
+        // choose a direction and the attenuation makes up the difference.
 
-        let r0 = ((1. - refractive_index_ratio) / (1. + refractive_index_ratio)).powi(2);
-        let reflectance = r0 + (1. - r0) * (1. - cos_theta).powi(5);
+        let (reflectance, reflectance_color) = match self.thin_film {
+            Some(thin_film) => {
+                let (outside_refractive_index, substrate_refractive_index) = if hit.is_front {
+                    (1., refractive_index)
+                } else {
+                    (refractive_index, 1.)
+                };
 
-        let direction = if is_total_internal_reflection || reflectance > rand::random::<f32>() {
-            V3::reflect(unit_direction, hit.normal)
-        } else {
-            V3::refract(unit_direction, hit.normal, refractive_index_ratio)
+                let reflectance_color =
+                    evaluate_per_wavelength(ray_in.wavelength, |wavelength, _| {
+                        thin_film.reflectance(
+                            cos_theta,
+                            outside_refractive_index,
+                            Substrate::Dielectric(substrate_refractive_index),
+                            wavelength,
+                        )
+                    });
+
+                let reflectance =
+                    (reflectance_color.r + reflectance_color.g + reflectance_color.b) / 3.;
+
+                (reflectance, reflectance_color)
+            }
+            None => {
+                let r0 = ((1. - refractive_index_ratio) / (1. + refractive_index_ratio)).powi(2);
+                let reflectance = r0 + (1. - r0) * (1. - cos_theta).powi(5);
+
+                (reflectance, Color::gray(reflectance))
+            }
+        };
+
+        let (direction, tint) =
+            if is_total_internal_reflection || reflectance > rand::random::<f32>() {
+                (
+                    V3::reflect(unit_direction, hit.normal),
+                    if is_total_internal_reflection {
+                        Color::gray(1.)
+                    } else {
+                        (1. / reflectance) * reflectance_color
+                    },
+                )
+            } else {
+                (
+                    V3::refract(unit_direction, hit.normal, refractive_index_ratio),
+                    (1. / (1. - reflectance)) * (Color::gray(1.) - reflectance_color),
+                )
+            };
+
+        let transmittance = match self.absorption {
+            Some(absorption) => absorption.transmittance(ray_in, hit),
+            None => Color::gray(1.),
         };
 
         Some(ScatterResult {
@@ -40,12 +104,10 @@ impl Material for DialectricMaterial {
                 position: hit.position,
                 direction,
                 time: ray_in.time,
+                wavelength: ray_in.wavelength,
             },
-            attenuation: Color {
-                r: 1.,
-                g: 1.,
-                b: 1.,
-            },
+            attenuation: tint * transmittance,
+            interior_medium: None,
         })
     }
 }
@@ -0,0 +1,44 @@
+use serde::Deserialize;
+
+// NOTE - Wavelength-dependent refractive index. Wavelengths are given in nanometers, while the
+// coefficients use micrometers as is conventional.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum Dispersion {
+    Cauchy {
+        a: f32,
+        b: f32,
+    },
+    Sellmeier {
+        b1: f32,
+        b2: f32,
+        b3: f32,
+        c1: f32,
+        c2: f32,
+        c3: f32,
+    },
+}
+
+impl Dispersion {
+    pub fn refractive_index(&self, wavelength: f32) -> f32 {
+        let wavelength = wavelength / 1000.;
+        let wavelength2 = wavelength * wavelength;
+
+        match self {
+            Dispersion::Cauchy { a, b } => a + b / wavelength2,
+            Dispersion::Sellmeier {
+                b1,
+                b2,
+                b3,
+                c1,
+                c2,
+                c3,
+            } => (1.
+                + b1 * wavelength2 / (wavelength2 - c1)
+                + b2 * wavelength2 / (wavelength2 - c2)
+                + b3 * wavelength2 / (wavelength2 - c3))
+                .sqrt(),
+        }
+    }
+}
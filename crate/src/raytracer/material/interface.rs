@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+use crate::raytracer::{color::Color, medium::MediumKind, object::Hit, ray::Ray};
+
+use super::{Material, ScatterResult};
+
+// NOTE - An invisible boundary, which lets rays pass through unchanged. Used to delimit
+// participating media, which objects such as volumes attach when they are built.
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct InterfaceMaterial {
+    #[serde(skip)]
+    pub medium: Option<MediumKind>,
+}
+
+impl Material for InterfaceMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        Some(ScatterResult {
+            ray_out: Ray {
+                position: hit.position,
+                direction: ray_in.direction,
+                time: ray_in.time,
+                wavelength: ray_in.wavelength,
+            },
+            attenuation: Color::gray(1.),
+            interior_medium: self.medium.clone(),
+        })
+    }
+}
@@ -27,8 +27,10 @@ impl Material for LambertianMaterial {
                 position: hit.position,
                 direction,
                 time: ray_in.time,
+                wavelength: ray_in.wavelength,
             },
             attenuation: self.texture.value(hit.u, hit.v, hit.position),
+            interior_medium: None,
         })
     }
 }
@@ -1,13 +1,20 @@
 use serde::Deserialize;
 
-use crate::raytracer::{color::Color, object::Hit, ray::Ray, v3::V3};
+use crate::raytracer::{
+    color::Color, object::Hit, ray::Ray, spectrum::evaluate_per_wavelength, v3::V3,
+};
 
-use super::{Material, ScatterResult};
+use super::{
+    thin_film::{Substrate, ThinFilm},
+    Material, ScatterResult,
+};
 
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub struct MetalMaterial {
     pub albedo: Color,
     pub fuzzing_factor: f32,
+    #[serde(default)]
+    pub thin_film: Option<ThinFilm>,
 }
 
 impl Material for MetalMaterial {
@@ -19,15 +26,33 @@ impl Material for MetalMaterial {
             position: hit.position,
             direction: fuzzed_direction,
             time: ray_in.time,
+            wavelength: ray_in.wavelength,
         };
 
         if V3::dot(&ray_out.direction, &hit.normal) <= 0. {
             return None;
         }
 
+        let attenuation = match self.thin_film {
+            Some(thin_film) => {
+                let cos_theta = V3::dot(&-ray_in.direction.unit(), &hit.normal).clamp(0., 1.);
+
+                evaluate_per_wavelength(ray_in.wavelength, |wavelength, sample| {
+                    thin_film.reflectance(
+                        cos_theta,
+                        1.,
+                        Substrate::Albedo(sample(self.albedo)),
+                        wavelength,
+                    )
+                })
+            }
+            None => self.albedo,
+        };
+
         Some(ScatterResult {
             ray_out,
-            attenuation: self.albedo,
+            attenuation,
+            interior_medium: None,
         })
     }
 }
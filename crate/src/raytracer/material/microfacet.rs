@@ -0,0 +1,128 @@
+use std::f32::consts::PI;
+
+use crate::raytracer::v3::V3;
+
+// NOTE - Helpers for the GGX (Trowbridge-Reitz) microfacet distribution. All directions are in
+// the local shading frame, where the surface normal is +Z.
+
+const MINIMUM_ALPHA: f32 = 1e-3;
+
+pub fn roughness_to_alpha(roughness: f32) -> f32 {
+    (roughness * roughness).max(MINIMUM_ALPHA)
+}
+
+fn lambda(w: &V3, alpha: f32) -> f32 {
+    let cos2_theta = w.z * w.z;
+
+    if cos2_theta <= 0. {
+        return 0.;
+    }
+
+    let tan2_theta = (1. - cos2_theta).max(0.) / cos2_theta;
+
+    0.5 * (-1. + (1. + alpha * alpha * tan2_theta).sqrt())
+}
+
+fn smith_g1(w: &V3, alpha: f32) -> f32 {
+    1. / (1. + lambda(w, alpha))
+}
+
+fn smith_g2(wo: &V3, wi: &V3, alpha: f32) -> f32 {
+    1. / (1. + lambda(wo, alpha) + lambda(wi, alpha))
+}
+
+pub fn masking_weight(wo: &V3, wi: &V3, alpha: f32) -> f32 {
+    // NOTE - With visible normal sampling, the microfacet weight reduces to G2 / G1.
+
+    smith_g2(wo, wi, alpha) / smith_g1(wo, alpha)
+}
+
+pub fn sample_visible_normal(wo: &V3, alpha: f32) -> V3 {
+    // NOTE - Visible normal sampling from Heitz (2018). The view direction is stretched into the
+    // hemisphere configuration, a point is sampled on the projected disk, and the resulting
+    // normal is unstretched.
+
+    let vh = V3 {
+        x: alpha * wo.x,
+        y: alpha * wo.y,
+        z: wo.z,
+    }
+    .unit();
+
+    let length2 = vh.x * vh.x + vh.y * vh.y;
+
+    let t1 = if length2 > 0. {
+        V3 {
+            x: -vh.y,
+            y: vh.x,
+            z: 0.,
+        } / length2.sqrt()
+    } else {
+        V3 {
+            x: 1.,
+            y: 0.,
+            z: 0.,
+        }
+    };
+
+    let t2 = V3::cross(&vh, &t1);
+
+    let r = rand::random::<f32>().sqrt();
+    let phi = 2. * PI * rand::random::<f32>();
+
+    let p1 = r * phi.cos();
+    let s = 0.5 * (1. + vh.z);
+    let p2 = (1. - s) * (1. - p1 * p1).sqrt() + s * r * phi.sin();
+
+    let nh = p1 * t1 + p2 * t2 + (1. - p1 * p1 - p2 * p2).max(0.).sqrt() * vh;
+
+    V3 {
+        x: alpha * nh.x,
+        y: alpha * nh.y,
+        z: nh.z.max(0.),
+    }
+    .unit()
+}
+
+pub fn schlick_weight(cos_theta: f32) -> f32 {
+    (1. - cos_theta).clamp(0., 1.).powi(5)
+}
+
+pub fn fresnel_dielectric(cos_theta_i: f32, refractive_index_ratio: f32) -> f32 {
+    let sin2_theta_t =
+        refractive_index_ratio * refractive_index_ratio * (1. - cos_theta_i * cos_theta_i).max(0.);
+
+    if sin2_theta_t >= 1. {
+        return 1.;
+    }
+
+    let cos_theta_t = (1. - sin2_theta_t).sqrt();
+
+    let r_s = (refractive_index_ratio * cos_theta_i - cos_theta_t)
+        / (refractive_index_ratio * cos_theta_i + cos_theta_t);
+    let r_p = (cos_theta_i - refractive_index_ratio * cos_theta_t)
+        / (cos_theta_i + refractive_index_ratio * cos_theta_t);
+
+    0.5 * (r_s * r_s + r_p * r_p)
+}
+
+pub fn fresnel_conductor(cos_theta_i: f32, eta: f32, k: f32) -> f32 {
+    // NOTE - Exact unpolarized Fresnel reflectance for a conductor with complex refractive index
+    // eta + ik, following pbrt.
+
+    let cos2_theta_i = cos_theta_i * cos_theta_i;
+    let sin2_theta_i = 1. - cos2_theta_i;
+
+    let t0 = eta * eta - k * k - sin2_theta_i;
+    let a2_plus_b2 = (t0 * t0 + 4. * eta * eta * k * k).sqrt();
+    let t1 = a2_plus_b2 + cos2_theta_i;
+    let a = (0.5 * (a2_plus_b2 + t0)).max(0.).sqrt();
+    let t2 = 2. * cos_theta_i * a;
+    let r_s = (t1 - t2) / (t1 + t2);
+
+    let t3 = cos2_theta_i * a2_plus_b2 + sin2_theta_i * sin2_theta_i;
+    let t4 = t2 * sin2_theta_i;
+    let r_p = r_s * (t3 - t4) / (t3 + t4);
+
+    0.5 * (r_p + r_s)
+}
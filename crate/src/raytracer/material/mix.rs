@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::raytracer::{object::Hit, ray::Ray, texture::ScalarTexture, v3::V3};
+
+use super::{microfacet::fresnel_dielectric, Material, MaterialKind, ScatterResult};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum MixWeight {
+    Fresnel { refractive_index: f32 },
+    Texture { texture: ScalarTexture },
+}
+
+// NOTE - Stochastically chooses between two materials, picking the second with probability
+// equal to the weight.
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct MixMaterial {
+    pub material_a: Arc<MaterialKind>,
+    pub material_b: Arc<MaterialKind>,
+    pub weight: MixWeight,
+}
+
+impl MixMaterial {
+    fn choose(&self, ray_in: &Ray, hit: &Hit) -> &MaterialKind {
+        let weight = match self.weight {
+            MixWeight::Fresnel { refractive_index } => {
+                let cos_theta = V3::dot(&-ray_in.direction.unit(), &hit.normal).clamp(0., 1.);
+
+                let refractive_index_ratio = if hit.is_front {
+                    1. / refractive_index
+                } else {
+                    refractive_index
+                };
+
+                fresnel_dielectric(cos_theta, refractive_index_ratio)
+            }
+            MixWeight::Texture { texture } => texture.value(hit.u, hit.v, hit.position),
+        };
+
+        if weight > rand::random::<f32>() {
+            &self.material_b
+        } else {
+            &self.material_a
+        }
+    }
+}
+
+impl Material for MixMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        self.choose(ray_in, hit).scatter(ray_in, hit)
+    }
+}
@@ -1,22 +1,70 @@
 use serde::Deserialize;
 
-use self::{dialectric::DialectricMaterial, lambertian::LambertianMaterial, metal::MetalMaterial};
+use self::{
+    bumped::BumpedMaterial, conductor::ConductorMaterial, dialectric::DialectricMaterial,
+    lambertian::LambertianMaterial, metal::MetalMaterial, mix::MixMaterial,
+    oren_nayar::OrenNayarMaterial, principled::PrincipledMaterial,
+    rough_dialectric::RoughDialectricMaterial, sheen::SheenMaterial,
+    subsurface::SubsurfaceMaterial,
+};
 
-use super::{color::Color, object::Hit, ray::Ray, v3::P3};
+use super::{color::Color, medium::MediumKind, object::Hit, ray::Ray, v3::P3};
 
+mod absorption;
+mod bumped;
+mod conductor;
 mod dialectric;
+mod dispersion;
+mod interface;
 mod lambertian;
 mod metal;
+mod microfacet;
+mod mix;
+mod oren_nayar;
+mod principled;
+mod rough_dialectric;
+mod sheen;
+mod subsurface;
+mod thin_film;
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+pub use interface::InterfaceMaterial;
+
+#[derive(Clone, Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum MaterialKind {
+    Bumped(BumpedMaterial),
+    Conductor(ConductorMaterial),
     Dialectric(DialectricMaterial),
+    Interface(InterfaceMaterial),
     Lambertian(LambertianMaterial),
     Metal(MetalMaterial),
+    Mix(MixMaterial),
+    OrenNayar(OrenNayarMaterial),
+    Principled(PrincipledMaterial),
+    RoughDialectric(RoughDialectricMaterial),
+    Sheen(SheenMaterial),
+    Subsurface(SubsurfaceMaterial),
 }
 
 impl MaterialKind {
+    pub fn is_wavelength_dependent(&self) -> bool {
+        match self {
+            MaterialKind::Bumped(x) => x.material.is_wavelength_dependent(),
+            MaterialKind::Conductor(x) => x.thin_film.is_some(),
+            MaterialKind::Dialectric(x) => x.dispersion.is_some() || x.thin_film.is_some(),
+            MaterialKind::Metal(x) => x.thin_film.is_some(),
+            MaterialKind::Mix(x) => {
+                x.material_a.is_wavelength_dependent() || x.material_b.is_wavelength_dependent()
+            }
+            MaterialKind::RoughDialectric(x) => x.dispersion.is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn is_null_boundary(&self) -> bool {
+        matches!(self, MaterialKind::Interface(_))
+    }
+
     pub fn emit(&self, _u: f32, _v: f32, _position: P3) -> Color {
         match self {
             _ => Color {
@@ -31,6 +79,7 @@ impl MaterialKind {
 pub struct ScatterResult {
     pub ray_out: Ray,
     pub attenuation: Color,
+    pub interior_medium: Option<MediumKind>,
 }
 
 pub trait Material {
@@ -40,9 +89,18 @@ pub trait Material {
 impl Material for MaterialKind {
     fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
         match self {
+            MaterialKind::Bumped(x) => x.scatter(ray_in, hit),
+            MaterialKind::Conductor(x) => x.scatter(ray_in, hit),
             MaterialKind::Dialectric(x) => x.scatter(ray_in, hit),
+            MaterialKind::Interface(x) => x.scatter(ray_in, hit),
             MaterialKind::Lambertian(x) => x.scatter(ray_in, hit),
             MaterialKind::Metal(x) => x.scatter(ray_in, hit),
+            MaterialKind::Mix(x) => x.scatter(ray_in, hit),
+            MaterialKind::OrenNayar(x) => x.scatter(ray_in, hit),
+            MaterialKind::Principled(x) => x.scatter(ray_in, hit),
+            MaterialKind::RoughDialectric(x) => x.scatter(ray_in, hit),
+            MaterialKind::Sheen(x) => x.scatter(ray_in, hit),
+            MaterialKind::Subsurface(x) => x.scatter(ray_in, hit),
         }
     }
 }
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+
+use crate::raytracer::{
+    frame::Frame,
+    object::Hit,
+    ray::Ray,
+    texture::{ScalarTexture, Texture, TextureKind},
+    v3::V3,
+};
+
+use super::{Material, ScatterResult};
+
+// NOTE - Rough diffuse reflection from Oren and Nayar (1994). The roughness is the standard
+// deviation of the microfacet slope angle in radians, where zero is Lambertian.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct OrenNayarMaterial {
+    pub texture: TextureKind,
+    pub roughness: ScalarTexture,
+}
+
+impl Material for OrenNayarMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let mut direction = hit.normal + V3::random_in_sphere(1.);
+
+        if direction.is_near_zero() {
+            direction = hit.normal;
+        }
+
+        let frame = Frame::from_normal(hit.normal);
+
+        let wo = frame.to_local(&-ray_in.direction.unit());
+        let wi = frame.to_local(&direction.unit());
+
+        let sigma = self.roughness.value(hit.u, hit.v, hit.position).max(0.);
+        let sigma2 = sigma * sigma;
+
+        let a = 1. - 0.5 * sigma2 / (sigma2 + 0.33);
+        let b = 0.45 * sigma2 / (sigma2 + 0.09);
+
+        let cos_theta_o = wo.z.abs().min(1.);
+        let cos_theta_i = wi.z.abs().min(1.);
+        let sin_theta_o = (1. - cos_theta_o * cos_theta_o).sqrt();
+        let sin_theta_i = (1. - cos_theta_i * cos_theta_i).sqrt();
+
+        let cos_delta_phi = if sin_theta_o > 1e-4 && sin_theta_i > 1e-4 {
+            ((wo.x * wi.x + wo.y * wi.y) / (sin_theta_o * sin_theta_i)).clamp(-1., 1.)
+        } else {
+            0.
+        };
+
+        // NOTE - Alpha is the larger of the two polar angles and beta the smaller.
+
+        let (sin_alpha, tan_beta) = if cos_theta_i > cos_theta_o {
+            (sin_theta_o, sin_theta_i / cos_theta_i)
+        } else {
+            (sin_theta_i, sin_theta_o / cos_theta_o.max(1e-4))
+        };
+
+        // NOTE - Directions are cosine-weighted, so the cosine and 1 / PI terms cancel.
+
+        let factor = a + b * cos_delta_phi.max(0.) * sin_alpha * tan_beta;
+
+        Some(ScatterResult {
+            ray_out: Ray {
+                position: hit.position,
+                direction,
+                time: ray_in.time,
+                wavelength: ray_in.wavelength,
+            },
+            attenuation: factor * self.texture.value(hit.u, hit.v, hit.position),
+            interior_medium: None,
+        })
+    }
+}
@@ -0,0 +1,225 @@
+use serde::Deserialize;
+
+use crate::raytracer::{
+    color::Color,
+    frame::Frame,
+    object::Hit,
+    ray::Ray,
+    texture::{ScalarTexture, Texture, TextureKind},
+    v3::V3,
+};
+
+use super::{
+    microfacet::{
+        fresnel_dielectric, masking_weight, roughness_to_alpha, sample_visible_normal,
+        schlick_weight,
+    },
+    Material, ScatterResult,
+};
+
+const CLEARCOAT_ROUGHNESS: f32 = 0.1;
+const CLEARCOAT_REFRACTIVE_INDEX: f32 = 1.5;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct PrincipledMaterial {
+    pub base_color: TextureKind,
+    pub metallic: ScalarTexture,
+    pub roughness: ScalarTexture,
+    pub specular: ScalarTexture,
+    #[serde(default)]
+    pub specular_tint: ScalarTexture,
+    #[serde(default)]
+    pub sheen: ScalarTexture,
+    #[serde(default)]
+    pub clearcoat: ScalarTexture,
+    #[serde(default)]
+    pub transmission: ScalarTexture,
+    pub refractive_index: ScalarTexture,
+}
+
+impl Material for PrincipledMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let (u, v, position) = (hit.u, hit.v, hit.position);
+
+        let base_color = self.base_color.value(u, v, position);
+        let metallic = self.metallic.value(u, v, position).clamp(0., 1.);
+        let roughness = self.roughness.value(u, v, position).clamp(0., 1.);
+        let specular = self.specular.value(u, v, position).max(0.);
+        let specular_tint = self.specular_tint.value(u, v, position).clamp(0., 1.);
+        let sheen = self.sheen.value(u, v, position).max(0.);
+        let clearcoat = self.clearcoat.value(u, v, position).clamp(0., 1.);
+        let transmission = self.transmission.value(u, v, position).clamp(0., 1.);
+        let refractive_index = self.refractive_index.value(u, v, position);
+
+        let frame = Frame::from_normal(hit.normal);
+        let wo = frame.to_local(&-ray_in.direction.unit());
+
+        if wo.z <= 0. {
+            return None;
+        }
+
+        // NOTE - Each lobe is chosen stochastically in proportion to its contribution, so the
+        // selection probabilities cancel out of the attenuation. The clearcoat sits on top of
+        // the base layer, which is a blend of metal, glass and plastic.
+
+        let clearcoat_reflectance =
+            clearcoat * fresnel_dielectric(wo.z, 1. / CLEARCOAT_REFRACTIVE_INDEX);
+
+        if clearcoat_reflectance > rand::random::<f32>() {
+            return sample_reflection(
+                ray_in,
+                hit,
+                &frame,
+                &wo,
+                roughness_to_alpha(CLEARCOAT_ROUGHNESS),
+                |_| Color::gray(1.),
+            );
+        }
+
+        let alpha = roughness_to_alpha(roughness);
+
+        if metallic > rand::random::<f32>() {
+            return sample_reflection(ray_in, hit, &frame, &wo, alpha, |cos_theta| {
+                Color::lerp(base_color, Color::gray(1.), schlick_weight(cos_theta))
+            });
+        }
+
+        let luminance = base_color.luminance();
+
+        let tint_color = if luminance > 0. {
+            (1. / luminance) * base_color
+        } else {
+            Color::gray(1.)
+        };
+
+        let specular_color = Color::lerp(Color::gray(1.), tint_color, specular_tint);
+
+        if transmission > rand::random::<f32>() {
+            return sample_transmission(
+                ray_in,
+                hit,
+                &frame,
+                &wo,
+                alpha,
+                refractive_index,
+                base_color,
+            );
+        }
+
+        // NOTE - The dielectric specular reflectance at normal incidence is remapped so that the
+        // default specular of 0.5 corresponds to a refractive index of 1.5.
+
+        let f0 = 0.08 * specular;
+        let specular_reflectance = f0 + (1. - f0) * schlick_weight(wo.z);
+
+        if specular_reflectance > rand::random::<f32>() {
+            return sample_reflection(ray_in, hit, &frame, &wo, alpha, |_| specular_color);
+        }
+
+        let mut direction = hit.normal + V3::random_in_sphere(1.);
+
+        if direction.is_near_zero() {
+            direction = hit.normal;
+        }
+
+        let wi = frame.to_local(&direction.unit());
+        let cos_theta_d = V3::dot(&wi, &(wi + wo).unit());
+
+        Some(ScatterResult {
+            ray_out: Ray {
+                position: hit.position,
+                direction,
+                time: ray_in.time,
+                wavelength: ray_in.wavelength,
+            },
+            attenuation: base_color + (sheen * schlick_weight(cos_theta_d)) * tint_color,
+            interior_medium: None,
+        })
+    }
+}
+
+fn sample_reflection<F>(
+    ray_in: &Ray,
+    hit: &Hit,
+    frame: &Frame,
+    wo: &V3,
+    alpha: f32,
+    fresnel: F,
+) -> Option<ScatterResult>
+where
+    F: Fn(f32) -> Color,
+{
+    let m = sample_visible_normal(wo, alpha);
+    let wi = V3::reflect(-*wo, m);
+
+    if wi.z <= 0. {
+        return None;
+    }
+
+    let cos_theta = V3::dot(wo, &m).max(0.);
+
+    Some(ScatterResult {
+        ray_out: Ray {
+            position: hit.position,
+            direction: frame.to_world(&wi),
+            time: ray_in.time,
+            wavelength: ray_in.wavelength,
+        },
+        attenuation: masking_weight(wo, &wi, alpha) * fresnel(cos_theta),
+        interior_medium: None,
+    })
+}
+
+fn sample_transmission(
+    ray_in: &Ray,
+    hit: &Hit,
+    frame: &Frame,
+    wo: &V3,
+    alpha: f32,
+    refractive_index: f32,
+    transmission_color: Color,
+) -> Option<ScatterResult> {
+    let refractive_index_ratio = if hit.is_front {
+        1. / refractive_index
+    } else {
+        refractive_index
+    };
+
+    let m = sample_visible_normal(wo, alpha);
+    let cos_theta = V3::dot(wo, &m).max(0.);
+
+    let reflectance = fresnel_dielectric(cos_theta, refractive_index_ratio);
+
+    let is_reflection = reflectance > rand::random::<f32>();
+
+    // NOTE - A path refracts into and back out of the object, so only tint it on the way in.
+    // Otherwise the color would be applied twice.
+
+    let (wi, tint) = if is_reflection {
+        (V3::reflect(-*wo, m), Color::gray(1.))
+    } else {
+        (
+            V3::refract(-*wo, m, refractive_index_ratio).unit(),
+            if hit.is_front {
+                transmission_color
+            } else {
+                Color::gray(1.)
+            },
+        )
+    };
+
+    if (wi.z > 0.) != is_reflection {
+        return None;
+    }
+
+    Some(ScatterResult {
+        ray_out: Ray {
+            position: hit.position,
+            direction: frame.to_world(&wi),
+            time: ray_in.time,
+            wavelength: ray_in.wavelength,
+        },
+        attenuation: masking_weight(wo, &wi, alpha) * tint,
+        interior_medium: None,
+    })
+}
@@ -0,0 +1,88 @@
+use serde::Deserialize;
+
+use crate::raytracer::{color::Color, frame::Frame, object::Hit, ray::Ray, v3::V3};
+
+use super::{
+    absorption::Absorption,
+    dispersion::Dispersion,
+    microfacet::{fresnel_dielectric, masking_weight, roughness_to_alpha, sample_visible_normal},
+    Material, ScatterResult,
+};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct RoughDialectricMaterial {
+    pub refractive_index: f32,
+    pub roughness: f32,
+    #[serde(default)]
+    pub absorption: Option<Absorption>,
+    #[serde(default)]
+    pub dispersion: Option<Dispersion>,
+}
+
+impl Material for RoughDialectricMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let refractive_index = match (self.dispersion, ray_in.wavelength) {
+            (Some(dispersion), Some(wavelength)) => dispersion.refractive_index(wavelength),
+            _ => self.refractive_index,
+        };
+
+        let refractive_index_ratio = if hit.is_front {
+            1. / refractive_index
+        } else {
+            refractive_index
+        };
+
+        let frame = Frame::from_normal(hit.normal);
+        let alpha = roughness_to_alpha(self.roughness);
+
+        let wo = frame.to_local(&-ray_in.direction.unit());
+
+        if wo.z <= 0. {
+            return None;
+        }
+
+        let m = sample_visible_normal(&wo, alpha);
+        let cos_theta = V3::dot(&wo, &m).max(0.);
+
+        // NOTE - Choose between reflection and refraction in proportion to the Fresnel term, so
+        // that it cancels out of the weight.
+
+        let reflectance = fresnel_dielectric(cos_theta, refractive_index_ratio);
+
+        let wi = if reflectance > rand::random::<f32>() {
+            let wi = V3::reflect(-wo, m);
+
+            if wi.z <= 0. {
+                return None;
+            }
+
+            wi
+        } else {
+            let wi = V3::refract(-wo, m, refractive_index_ratio).unit();
+
+            if wi.z >= 0. {
+                return None;
+            }
+
+            wi
+        };
+
+        let masking = masking_weight(&wo, &wi, alpha);
+
+        let transmittance = match self.absorption {
+            Some(absorption) => absorption.transmittance(ray_in, hit),
+            None => Color::gray(1.),
+        };
+
+        Some(ScatterResult {
+            ray_out: Ray {
+                position: hit.position,
+                direction: frame.to_world(&wi),
+                time: ray_in.time,
+                wavelength: ray_in.wavelength,
+            },
+            attenuation: masking * transmittance,
+            interior_medium: None,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use std::f32::consts::PI;
+
+use serde::Deserialize;
+
+use crate::raytracer::{
+    frame::Frame,
+    object::Hit,
+    ray::Ray,
+    texture::{ScalarTexture, Texture, TextureKind},
+    v3::V3,
+};
+
+use super::{microfacet::roughness_to_alpha, Material, ScatterResult};
+
+// NOTE - Cloth sheen using the "Charlie" distribution from Estevez and Kulla (2017) and the
+// visibility term from Neubelt and Pettineo (2013). Reflection is strongest at grazing angles,
+// which gives fabrics their soft, fuzzy rim.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct SheenMaterial {
+    pub color: TextureKind,
+    pub roughness: ScalarTexture,
+}
+
+impl Material for SheenMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let mut direction = hit.normal + V3::random_in_sphere(1.);
+
+        if direction.is_near_zero() {
+            direction = hit.normal;
+        }
+
+        let frame = Frame::from_normal(hit.normal);
+
+        let wo = frame.to_local(&-ray_in.direction.unit());
+        let wi = frame.to_local(&direction.unit());
+
+        if wo.z <= 0. || wi.z <= 0. {
+            return None;
+        }
+
+        let roughness = self
+            .roughness
+            .value(hit.u, hit.v, hit.position)
+            .clamp(0., 1.);
+        let alpha = roughness_to_alpha(roughness);
+
+        let h = (wo + wi).unit();
+        let sin_theta_h = (1. - h.z * h.z).max(0.).sqrt();
+
+        let distribution = (2. + 1. / alpha) * sin_theta_h.powf(1. / alpha) / (2. * PI);
+        let visibility = 1. / (4. * (wi.z + wo.z - wi.z * wo.z));
+
+        // NOTE - Directions are cosine-weighted, so the weight is the BRDF times PI.
+
+        Some(ScatterResult {
+            ray_out: Ray {
+                position: hit.position,
+                direction,
+                time: ray_in.time,
+                wavelength: ray_in.wavelength,
+            },
+            attenuation: (PI * distribution * visibility)
+                * self.color.value(hit.u, hit.v, hit.position),
+            interior_medium: None,
+        })
+    }
+}
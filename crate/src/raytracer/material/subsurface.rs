@@ -0,0 +1,82 @@
+use serde::Deserialize;
+
+use crate::raytracer::{
+    color::Color,
+    medium::{HomogeneousMedium, MediumKind},
+    object::Hit,
+    phase_function::PhaseFunctionKind,
+    ray::Ray,
+};
+
+use super::{dialectric::DialectricMaterial, Material, ScatterResult};
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum SubsurfaceCoefficients {
+    Coefficients {
+        absorption: Color,
+        scattering: Color,
+    },
+    MeanFreePath {
+        albedo: Color,
+        mean_free_path: Color,
+    },
+}
+
+// NOTE - A smooth dielectric boundary around a scattering medium. Light that refracts into the
+// object performs a random walk through the medium until it refracts back out.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct SubsurfaceMaterial {
+    pub refractive_index: f32,
+    pub coefficients: SubsurfaceCoefficients,
+    #[serde(default)]
+    pub phase_function: PhaseFunctionKind,
+}
+
+impl SubsurfaceMaterial {
+    pub fn medium(&self) -> MediumKind {
+        let (absorption, scattering) = match self.coefficients {
+            SubsurfaceCoefficients::Coefficients {
+                absorption,
+                scattering,
+            } => (absorption, scattering),
+            SubsurfaceCoefficients::MeanFreePath {
+                albedo,
+                mean_free_path,
+            } => {
+                let extinction = Color {
+                    r: 1. / mean_free_path.r,
+                    g: 1. / mean_free_path.g,
+                    b: 1. / mean_free_path.b,
+                };
+
+                ((Color::gray(1.) - albedo) * extinction, albedo * extinction)
+            }
+        };
+
+        MediumKind::Homogeneous(HomogeneousMedium {
+            absorption,
+            scattering,
+            phase_function: self.phase_function,
+        })
+    }
+}
+
+impl Material for SubsurfaceMaterial {
+    fn scatter(&self, ray_in: &Ray, hit: &Hit) -> Option<ScatterResult> {
+        let boundary = DialectricMaterial {
+            refractive_index: self.refractive_index,
+            absorption: None,
+            dispersion: None,
+            thin_film: None,
+        };
+
+        // NOTE - The boundary only refracts, so the medium is attached to its result here.
+
+        boundary.scatter(ray_in, hit).map(|scatter| ScatterResult {
+            interior_medium: Some(self.medium()),
+            ..scatter
+        })
+    }
+}
@@ -0,0 +1,87 @@
+use std::f32::consts::PI;
+
+use serde::Deserialize;
+
+use super::microfacet::fresnel_conductor;
+
+// NOTE - A thin transparent coating on top of a surface. Light reflected off the top and bottom
+// of the film interferes, so reflectance varies with wavelength, film thickness and angle.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct ThinFilm {
+    pub thickness: f32,
+    pub refractive_index: f32,
+}
+
+pub enum Substrate {
+    Dielectric(f32),
+    Conductor(f32, f32),
+    Albedo(f32),
+}
+
+impl ThinFilm {
+    pub fn reflectance(
+        &self,
+        cos_theta_i: f32,
+        outside_refractive_index: f32,
+        substrate: Substrate,
+        wavelength: f32,
+    ) -> f32 {
+        let n1 = outside_refractive_index;
+        let n2 = self.refractive_index;
+
+        let sin2_theta_i = (1. - cos_theta_i * cos_theta_i).max(0.);
+        let sin2_theta_film = (n1 / n2).powi(2) * sin2_theta_i;
+
+        if sin2_theta_film >= 1. {
+            return 1.;
+        }
+
+        let cos_theta_film = (1. - sin2_theta_film).sqrt();
+
+        let (r12_s, r12_p) = fresnel_amplitudes(n1, cos_theta_i, n2, cos_theta_film);
+
+        // NOTE - For conductors and plain albedos the phase shift on reflection is approximated
+        // as PI, which holds well for highly reflective substrates.
+
+        let (r23_s, r23_p) = match substrate {
+            Substrate::Dielectric(n3) => {
+                let sin2_theta_t = (n2 / n3).powi(2) * sin2_theta_film;
+
+                if sin2_theta_t >= 1. {
+                    (1., 1.)
+                } else {
+                    fresnel_amplitudes(n2, cos_theta_film, n3, (1. - sin2_theta_t).sqrt())
+                }
+            }
+            Substrate::Conductor(eta, k) => {
+                let r = -fresnel_conductor(cos_theta_film, eta / n2, k / n2).sqrt();
+
+                (r, r)
+            }
+            Substrate::Albedo(albedo) => {
+                let r = -albedo.clamp(0., 1.).sqrt();
+
+                (r, r)
+            }
+        };
+
+        let phase_difference = 4. * PI * n2 * self.thickness * cos_theta_film / wavelength;
+
+        0.5 * (airy_reflectance(r12_s, r23_s, phase_difference)
+            + airy_reflectance(r12_p, r23_p, phase_difference))
+    }
+}
+
+fn fresnel_amplitudes(n_a: f32, cos_theta_a: f32, n_b: f32, cos_theta_b: f32) -> (f32, f32) {
+    let r_s = (n_a * cos_theta_a - n_b * cos_theta_b) / (n_a * cos_theta_a + n_b * cos_theta_b);
+    let r_p = (n_b * cos_theta_a - n_a * cos_theta_b) / (n_b * cos_theta_a + n_a * cos_theta_b);
+
+    (r_s, r_p)
+}
+
+fn airy_reflectance(r12: f32, r23: f32, phase_difference: f32) -> f32 {
+    let cross_term = 2. * r12 * r23 * phase_difference.cos();
+
+    (r12 * r12 + r23 * r23 + cross_term) / (1. + r12 * r12 * r23 * r23 + cross_term)
+}
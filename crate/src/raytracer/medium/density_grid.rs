@@ -0,0 +1,252 @@
+use std::{convert::TryFrom, sync::Arc};
+
+use serde::Deserialize;
+
+use crate::raytracer::{noise::fractal_noise, v3::V3};
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum DensityGridSource {
+    Constant {
+        density: f32,
+    },
+    Noise {
+        resolution: u32,
+        frequency: f32,
+        octaves: u32,
+        seed: u32,
+    },
+    Raw {
+        data: Vec<u8>,
+    },
+    // NOTE - Only the command line renderer can read from disk. Relative paths are resolved
+    // against the working directory.
+    #[cfg(feature = "cli")]
+    File {
+        path: String,
+    },
+}
+
+// NOTE - A dense grid of densities covering the unit cube. Lookups use normalized coordinates
+// and trilinear interpolation between cell centers.
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "DensityGridSource")]
+pub struct DensityGrid {
+    pub size_x: usize,
+    pub size_y: usize,
+    pub size_z: usize,
+    pub maximum: f32,
+    values: Arc<Vec<f32>>,
+}
+
+impl DensityGrid {
+    fn new(size_x: usize, size_y: usize, size_z: usize, values: Vec<f32>) -> DensityGrid {
+        let maximum = values.iter().cloned().fold(0., f32::max);
+
+        DensityGrid {
+            size_x,
+            size_y,
+            size_z,
+            maximum,
+            values: Arc::new(values),
+        }
+    }
+
+    pub fn lookup(&self, uvw: &V3) -> f32 {
+        let (x0, x1, tx) = cell_coordinates(uvw.x, self.size_x);
+        let (y0, y1, ty) = cell_coordinates(uvw.y, self.size_y);
+        let (z0, z1, tz) = cell_coordinates(uvw.z, self.size_z);
+
+        let mut value = 0.;
+
+        for (x, wx) in [(x0, 1. - tx), (x1, tx)] {
+            for (y, wy) in [(y0, 1. - ty), (y1, ty)] {
+                for (z, wz) in [(z0, 1. - tz), (z1, tz)] {
+                    value += wx * wy * wz * self.get(x, y, z);
+                }
+            }
+        }
+
+        value
+    }
+
+    fn get(&self, x: usize, y: usize, z: usize) -> f32 {
+        self.values[(z * self.size_y + y) * self.size_x + x]
+    }
+}
+
+impl TryFrom<DensityGridSource> for DensityGrid {
+    type Error = String;
+
+    fn try_from(source: DensityGridSource) -> Result<Self, Self::Error> {
+        match source {
+            DensityGridSource::Constant { density } => Ok(DensityGrid::new(1, 1, 1, vec![density])),
+            DensityGridSource::Noise {
+                resolution,
+                frequency,
+                octaves,
+                seed,
+            } => {
+                let size = resolution.max(1) as usize;
+
+                let mut values = Vec::with_capacity(size * size * size);
+
+                for z in 0..size {
+                    for y in 0..size {
+                        for x in 0..size {
+                            let position = V3 {
+                                x: (x as f32 + 0.5) / size as f32,
+                                y: (y as f32 + 0.5) / size as f32,
+                                z: (z as f32 + 0.5) / size as f32,
+                            };
+
+                            values.push(fractal_noise(frequency * position, octaves, seed));
+                        }
+                    }
+                }
+
+                Ok(DensityGrid::new(size, size, size, values))
+            }
+            DensityGridSource::Raw { data } => parse_raw(&data),
+            #[cfg(feature = "cli")]
+            DensityGridSource::File { path } => {
+                let data = std::fs::read(&path)
+                    .map_err(|error| format!("Failed to read density grid {}: {}", path, error))?;
+
+                parse_raw(&data)
+            }
+        }
+    }
+}
+
+fn parse_raw(data: &[u8]) -> Result<DensityGrid, String> {
+    // NOTE - The raw format is three little-endian u32 dimensions followed by the little-endian
+    // f32 densities, with x varying fastest.
+
+    let read_u32 = |offset: usize| -> Result<u32, String> {
+        data.get(offset..offset + 4)
+            .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .ok_or_else(|| "Density grid header is truncated".to_string())
+    };
+
+    let size_x = read_u32(0)? as usize;
+    let size_y = read_u32(4)? as usize;
+    let size_z = read_u32(8)? as usize;
+
+    let count = size_x
+        .checked_mul(size_y)
+        .and_then(|count| count.checked_mul(size_z))
+        .ok_or_else(|| "Density grid dimensions are too large".to_string())?;
+
+    if count == 0 {
+        return Err("Density grid dimensions must be non-zero".to_string());
+    }
+
+    let body = &data[12..];
+
+    if body.len() != 4 * count {
+        return Err(format!(
+            "Density grid expected {} values but found {} bytes",
+            count,
+            body.len()
+        ));
+    }
+
+    let values = body
+        .chunks_exact(4)
+        .map(|bytes| f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).max(0.))
+        .collect();
+
+    Ok(DensityGrid::new(size_x, size_y, size_z, values))
+}
+
+fn cell_coordinates(t: f32, size: usize) -> (usize, usize, f32) {
+    let x = (t * size as f32 - 0.5).clamp(0., (size - 1) as f32);
+    let x0 = x.floor() as usize;
+    let x1 = (x0 + 1).min(size - 1);
+
+    (x0, x1, x - x0 as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn raw(dimensions: [u32; 3], values: &[f32]) -> Vec<u8> {
+        dimensions
+            .iter()
+            .flat_map(|dimension| dimension.to_le_bytes())
+            .chain(values.iter().flat_map(|value| value.to_le_bytes()))
+            .collect()
+    }
+
+    #[test]
+    fn parse_raw_reads_dimensions_and_values() {
+        let grid = parse_raw(&raw([2, 1, 1], &[0.25, 0.75])).unwrap();
+
+        assert_eq!((grid.size_x, grid.size_y, grid.size_z), (2, 1, 1));
+        assert_eq!(grid.maximum, 0.75);
+        assert_eq!(grid.get(0, 0, 0), 0.25);
+        assert_eq!(grid.get(1, 0, 0), 0.75);
+    }
+
+    #[test]
+    fn parse_raw_clamps_negative_densities() {
+        let grid = parse_raw(&raw([1, 1, 1], &[-1.])).unwrap();
+
+        assert_eq!(grid.get(0, 0, 0), 0.);
+    }
+
+    #[test]
+    fn parse_raw_rejects_truncated_header() {
+        assert!(parse_raw(&[]).is_err());
+        assert!(parse_raw(&raw([1, 1, 1], &[])[..11]).is_err());
+    }
+
+    #[test]
+    fn parse_raw_rejects_zero_sized_grid() {
+        assert!(parse_raw(&raw([0, 1, 1], &[])).is_err());
+        assert!(parse_raw(&raw([1, 1, 0], &[])).is_err());
+    }
+
+    #[test]
+    fn parse_raw_rejects_length_mismatch() {
+        assert!(parse_raw(&raw([2, 2, 1], &[1., 1., 1.])).is_err());
+        assert!(parse_raw(&raw([1, 1, 1], &[1., 1.])).is_err());
+        assert!(parse_raw(&[raw([1, 1, 1], &[1.]), vec![0]].concat()).is_err());
+    }
+
+    #[test]
+    fn parse_raw_rejects_overflowing_dimensions() {
+        assert!(parse_raw(&raw([u32::MAX, u32::MAX, u32::MAX], &[])).is_err());
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn file_source_reads_raw_grid() {
+        let path = std::env::temp_dir().join("fermion_density_grid_test.raw");
+
+        std::fs::write(&path, raw([1, 2, 1], &[0.5, 1.5])).unwrap();
+
+        let grid = DensityGrid::try_from(DensityGridSource::File {
+            path: path.to_string_lossy().into_owned(),
+        })
+        .unwrap();
+
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((grid.size_x, grid.size_y, grid.size_z), (1, 2, 1));
+        assert_eq!(grid.get(0, 1, 0), 1.5);
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn file_source_reports_missing_file() {
+        let source = DensityGridSource::File {
+            path: "does/not/exist.raw".to_string(),
+        };
+
+        assert!(DensityGrid::try_from(source).is_err());
+    }
+}
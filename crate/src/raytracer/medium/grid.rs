@@ -0,0 +1,112 @@
+use crate::raytracer::{
+    color::Color,
+    phase_function::PhaseFunctionKind,
+    ray::Ray,
+    spectrum::{Spectrum, Wavelengths},
+    v3::{P3, V3},
+};
+
+use super::{density_grid::DensityGrid, Medium, MediumSample};
+
+#[derive(Clone, Debug)]
+pub struct GridMedium {
+    pub minimum: P3,
+    pub maximum: P3,
+    pub absorption: Color,
+    pub scattering: Color,
+    pub density: DensityGrid,
+    pub phase_function: PhaseFunctionKind,
+}
+
+impl GridMedium {
+    fn density_at(&self, position: &P3) -> f32 {
+        let extent = self.maximum - self.minimum;
+        let offset = *position - self.minimum;
+
+        self.density.lookup(&V3 {
+            x: offset.x / extent.x,
+            y: offset.y / extent.y,
+            z: offset.z / extent.z,
+        })
+    }
+}
+
+impl Medium for GridMedium {
+    fn phase_function(&self) -> PhaseFunctionKind {
+        self.phase_function
+    }
+
+    fn sample(&self, ray: &Ray, t_max: f32, wavelengths: &Wavelengths) -> MediumSample {
+        // NOTE - Delta tracking against a majorant of the extinction. Tentative collisions are
+        // classified as absorption, scattering or null collisions. For chromatic media the
+        // probabilities use the largest coefficient over all channels and the path weight
+        // corrects for the difference (spectral tracking, Kutz et al. 2017).
+
+        let absorption = wavelengths.upsample(self.absorption);
+        let scattering = wavelengths.upsample(self.scattering);
+
+        let majorant = self.density.maximum * (absorption + scattering).max_component();
+
+        if majorant <= 0. {
+            return MediumSample {
+                scatter_position: None,
+                weight: Spectrum::constant(1.),
+            };
+        }
+
+        let length = ray.direction.len();
+        let distance_max = t_max * length;
+
+        let mut distance = 0.;
+        let mut weight = wavelengths.upsample(Color::gray(1.));
+
+        loop {
+            distance -= (1. - rand::random::<f32>()).ln() / majorant;
+
+            if distance >= distance_max {
+                return MediumSample {
+                    scatter_position: None,
+                    weight,
+                };
+            }
+
+            let position = ray.at(distance / length);
+            let density = self.density_at(&position);
+
+            let sigma_a = absorption * density;
+            let sigma_s = scattering * density;
+            let sigma_n = Spectrum::constant(majorant) - sigma_a - sigma_s;
+
+            let p_a = (sigma_a * weight).max_component().max(0.);
+            let p_s = (sigma_s * weight).max_component().max(0.);
+            let p_n = (sigma_n * weight).max_component().max(0.);
+
+            let total = p_a + p_s + p_n;
+
+            if total <= 0. {
+                return MediumSample {
+                    scatter_position: None,
+                    weight: Spectrum::default(),
+                };
+            }
+
+            let xi = rand::random::<f32>() * total;
+
+            if xi < p_a {
+                return MediumSample {
+                    scatter_position: None,
+                    weight: Spectrum::default(),
+                };
+            }
+
+            if xi < p_a + p_s {
+                return MediumSample {
+                    scatter_position: Some(position),
+                    weight: weight * sigma_s / (majorant * p_s / total),
+                };
+            }
+
+            weight = weight * sigma_n / (majorant * p_n / total);
+        }
+    }
+}
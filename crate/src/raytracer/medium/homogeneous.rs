@@ -0,0 +1,73 @@
+use crate::raytracer::{
+    color::Color,
+    phase_function::PhaseFunctionKind,
+    ray::Ray,
+    spectrum::{Spectrum, Wavelengths},
+};
+
+use super::{Medium, MediumSample};
+
+#[derive(Clone, Copy, Debug)]
+pub struct HomogeneousMedium {
+    pub absorption: Color,
+    pub scattering: Color,
+    pub phase_function: PhaseFunctionKind,
+}
+
+impl Medium for HomogeneousMedium {
+    fn phase_function(&self) -> PhaseFunctionKind {
+        self.phase_function
+    }
+
+    fn sample(&self, ray: &Ray, t_max: f32, wavelengths: &Wavelengths) -> MediumSample {
+        // NOTE - Distances are sampled using the extinction of a randomly chosen channel, and
+        // weighted by the average probability over all channels (spectral MIS), so that
+        // chromatic media don't produce fireflies.
+
+        let scattering = wavelengths.upsample(self.scattering);
+        let extinction = wavelengths.upsample(self.absorption + self.scattering);
+
+        let length = ray.direction.len();
+        let distance_max = t_max * length;
+
+        let count = wavelengths.count();
+        let channel = ((rand::random::<f32>() * count as f32) as usize).min(count - 1);
+
+        let distance = if extinction.values[channel] > 0. {
+            -(1. - rand::random::<f32>()).ln() / extinction.values[channel]
+        } else {
+            f32::INFINITY
+        };
+
+        let is_scattered = distance < distance_max;
+        let distance = distance.min(distance_max);
+
+        let mut pdf = 0.;
+        let mut weight = Spectrum::default();
+
+        for (i, value) in weight.values.iter_mut().enumerate().take(count) {
+            let transmittance = (-extinction.values[i] * distance).exp();
+
+            if is_scattered {
+                pdf += extinction.values[i] * transmittance / count as f32;
+                *value = scattering.values[i] * transmittance;
+            } else {
+                pdf += transmittance / count as f32;
+                *value = transmittance;
+            }
+        }
+
+        MediumSample {
+            scatter_position: if is_scattered {
+                Some(ray.at(distance / length))
+            } else {
+                None
+            },
+            weight: if pdf > 0. {
+                weight / pdf
+            } else {
+                Spectrum::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use super::{
+    phase_function::PhaseFunctionKind,
+    ray::Ray,
+    spectrum::{Spectrum, Wavelengths},
+    v3::P3,
+};
+
+mod density_grid;
+mod grid;
+mod homogeneous;
+
+pub use density_grid::DensityGrid;
+pub use grid::GridMedium;
+pub use homogeneous::HomogeneousMedium;
+
+// NOTE - Participating media fill the interior of objects. A path inside a medium may scatter
+// before reaching the next surface, in which case the medium reports where.
+
+#[derive(Clone, Debug)]
+pub enum MediumKind {
+    Grid(GridMedium),
+    Homogeneous(HomogeneousMedium),
+}
+
+pub struct MediumSample {
+    pub scatter_position: Option<P3>,
+    pub weight: Spectrum,
+}
+
+pub trait Medium {
+    fn phase_function(&self) -> PhaseFunctionKind;
+    fn sample(&self, ray: &Ray, t_max: f32, wavelengths: &Wavelengths) -> MediumSample;
+}
+
+impl Medium for MediumKind {
+    fn phase_function(&self) -> PhaseFunctionKind {
+        match self {
+            MediumKind::Grid(x) => x.phase_function(),
+            MediumKind::Homogeneous(x) => x.phase_function(),
+        }
+    }
+
+    fn sample(&self, ray: &Ray, t_max: f32, wavelengths: &Wavelengths) -> MediumSample {
+        match self {
+            MediumKind::Grid(x) => x.sample(ray, t_max, wavelengths),
+            MediumKind::Homogeneous(x) => x.sample(ray, t_max, wavelengths),
+        }
+    }
+}
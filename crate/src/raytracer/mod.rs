@@ -1,17 +1,35 @@
 use std::f32::INFINITY;
 
-use self::{material::Material, object::Object, ray::Ray, v3::V3};
-
+use self::{
+    background::Background,
+    material::Material,
+    medium::{Medium, MediumKind},
+    object::Object,
+    ray::Ray,
+    spectrum::{Spectrum, Wavelengths},
+    v3::V3,
+};
+
+mod animation;
+mod aperture;
+mod background;
 mod camera;
 mod color;
+mod frame;
 mod material;
+mod medium;
+mod noise;
 mod object;
+mod phase_function;
 mod ray;
 mod scene;
+mod shutter;
+mod spectrum;
 mod texture;
 mod utils;
 mod v3;
 
+pub use phase_function::{PhaseFunction, PhaseFunctionKind};
 pub use scene::Scene;
 
 pub fn trace_ray(scene: &Scene, x: u32, y: u32) -> (u8, u8, u8) {
@@ -21,10 +39,32 @@ pub fn trace_ray(scene: &Scene, x: u32, y: u32) -> (u8, u8, u8) {
         let u = (y as f32 + rand::random::<f32>()) / (scene.width as f32 - 1.);
         let v = 1. - (x as f32 + rand::random::<f32>()) / (scene.height as f32 - 1.);
 
-        let ray = scene.camera.make_ray(u, v);
-        let sample_color = bounce_ray(scene, &ray, scene.max_depth);
+        let wavelengths = Wavelengths::new(scene.spectral);
+
+        // NOTE - Chromatic aberration images each wavelength at a different position, so only
+        // one of them can be traced through the lens.
+
+        let (lens_wavelength, throughput) = if scene.camera.chromatic_aberration != 0. {
+            let (wavelength, throughput) = wavelengths.select_single();
+
+            (Some(wavelength), throughput)
+        } else {
+            (wavelengths.hero(), Spectrum::constant(1.))
+        };
+
+        // NOTE - Some projections don't cover the whole image, in which case the sample is black.
+
+        let mut ray = match scene.camera.make_ray(u, v, lens_wavelength) {
+            Some(ray) => ray,
+            None => continue,
+        };
 
-        accumulated_color += sample_color;
+        ray.wavelength = wavelengths.hero();
+
+        let sample_radiance = bounce_ray(scene, ray, &wavelengths, throughput);
+
+        accumulated_color +=
+            (scene.camera.exposure() * wavelengths.to_color(sample_radiance)).as_v3();
     }
 
     accumulated_color.x = (accumulated_color.x / scene.samples_per_pixel as f32).sqrt();
@@ -38,34 +78,152 @@ pub fn trace_ray(scene: &Scene, x: u32, y: u32) -> (u8, u8, u8) {
     (r, g, b)
 }
 
-fn bounce_ray(scene: &Scene, ray: &Ray, depth: u32) -> V3 {
-    if depth <= 0 {
-        return V3::default();
-    }
+fn bounce_ray(
+    scene: &Scene,
+    ray_in: Ray,
+    wavelengths: &Wavelengths,
+    throughput_in: Spectrum,
+) -> Spectrum {
+    let mut accumulated_radiance = Spectrum::default();
+    let mut throughput = throughput_in;
 
-    let maybe_hit = scene.root_object.hit(ray, 0.001, INFINITY);
+    let mut ray = ray_in;
+    let mut medium: Option<MediumKind> = None;
 
-    if maybe_hit.is_none() {
-        return scene.background_color.as_v3();
-    }
+    let mut depth = 0;
+    let mut volume_depth = 0;
+
+    while depth < scene.max_depth && volume_depth < scene.max_volume_depth {
+        let maybe_hit = scene.root_object.hit(&ray, 0.001, INFINITY);
+
+        // NOTE - Inside a medium, the path may scatter before it reaches the next surface.
+
+        if let Some(medium) = &medium {
+            let t_max = maybe_hit.as_ref().map_or(INFINITY, |hit| hit.t);
+            let medium_sample = medium.sample(&ray, t_max, wavelengths);
+
+            throughput = throughput * medium_sample.weight;
+
+            if throughput.max_component() <= 0. {
+                break;
+            }
+
+            if let Some(scatter_position) = medium_sample.scatter_position {
+                match russian_roulette(scene, depth + volume_depth, throughput) {
+                    Some(x) => throughput = x,
+                    None => break,
+                }
+
+                ray = Ray {
+                    position: scatter_position,
+                    direction: medium.phase_function().sample(&ray.direction.unit()),
+                    time: ray.time,
+                    wavelength: ray.wavelength,
+                };
+
+                // NOTE - Medium events are counted separately from surface bounces, so long
+                // random walks don't use up the surface depth.
+
+                volume_depth += 1;
+
+                continue;
+            }
+        }
+
+        if maybe_hit.is_none() {
+            // NOTE - Camera rays may see a different background than the one lighting the scene.
+
+            let background = match scene.camera_background {
+                Some(camera_background) if depth == 0 && volume_depth == 0 => camera_background,
+                _ => scene.background,
+            };
 
-    let hit = maybe_hit.unwrap();
+            accumulated_radiance += throughput * wavelengths.upsample(background.value(&ray));
 
-    let emitted_color = hit.material.emit(hit.u, hit.v, hit.position);
+            break;
+        }
 
-    let maybe_scatter = hit.material.scatter(ray, &hit);
+        let hit = maybe_hit.unwrap();
 
-    if maybe_scatter.is_none() {
-        return emitted_color.as_v3();
+        // NOTE - Null boundaries only delimit media. Crossing one isn't a real bounce, so it
+        // doesn't count towards the depth or Russian roulette.
+
+        if hit.material.is_null_boundary() {
+            let maybe_scatter = hit.material.scatter(&ray, &hit);
+
+            if maybe_scatter.is_none() {
+                break;
+            }
+
+            let scatter = maybe_scatter.unwrap();
+
+            medium = if hit.is_front {
+                scatter.interior_medium
+            } else {
+                None
+            };
+
+            ray = scatter.ray_out;
+
+            continue;
+        }
+
+        let emitted_color = hit.material.emit(hit.u, hit.v, hit.position);
+
+        accumulated_radiance += throughput * wavelengths.upsample(emitted_color);
+
+        let maybe_scatter = hit.material.scatter(&ray, &hit);
+
+        if maybe_scatter.is_none() {
+            break;
+        }
+
+        let scatter = maybe_scatter.unwrap();
+
+        throughput = throughput * wavelengths.upsample(scatter.attenuation);
+
+        if hit.material.is_wavelength_dependent() {
+            throughput = wavelengths.terminate_secondary(throughput);
+        }
+
+        match russian_roulette(scene, depth + volume_depth, throughput) {
+            Some(x) => throughput = x,
+            None => break,
+        }
+
+        // NOTE - Transmission through a surface enters or leaves its interior medium.
+
+        if V3::dot(&scatter.ray_out.direction, &hit.normal) < 0.
+            && scatter.interior_medium.is_some()
+        {
+            medium = if hit.is_front {
+                scatter.interior_medium
+            } else {
+                None
+            };
+        }
+
+        ray = scatter.ray_out;
+        depth += 1;
     }
 
-    let scatter = maybe_scatter.unwrap();
+    accumulated_radiance
+}
+
+fn russian_roulette(scene: &Scene, depth: u32, throughput: Spectrum) -> Option<Spectrum> {
+    // NOTE - Paths that carry little throughput are terminated early, and the survivors are
+    // re-weighted to keep the estimate unbiased.
 
-    let scattered_color = emitted_color.as_v3()
-        + V3::hadamard(
-            &scatter.attenuation.as_v3(),
-            &bounce_ray(scene, &scatter.ray_out, depth - 1),
-        );
+    match scene.russian_roulette_depth {
+        Some(russian_roulette_depth) if depth >= russian_roulette_depth => {
+            let survival_probability = throughput.max_component().min(0.95);
 
-    scattered_color
+            if rand::random::<f32>() >= survival_probability {
+                return None;
+            }
+
+            Some(throughput / survival_probability)
+        }
+        _ => Some(throughput),
+    }
 }
@@ -0,0 +1,64 @@
+use super::v3::P3;
+
+// NOTE - Hash-based value noise. Lattice values are derived from the integer coordinates and a
+// seed, so no permutation tables need to be stored.
+
+pub fn fractal_noise(position: P3, octaves: u32, seed: u32) -> f32 {
+    let mut value = 0.;
+    let mut amplitude = 0.5;
+    let mut frequency = 1.;
+    let mut total_amplitude = 0.;
+
+    for octave in 0..octaves.max(1) {
+        value += amplitude * value_noise(frequency * position, seed.wrapping_add(octave));
+        total_amplitude += amplitude;
+
+        amplitude *= 0.5;
+        frequency *= 2.;
+    }
+
+    value / total_amplitude
+}
+
+fn value_noise(position: P3, seed: u32) -> f32 {
+    let (x0, y0, z0) = (position.x.floor(), position.y.floor(), position.z.floor());
+
+    let (tx, ty, tz) = (
+        smooth(position.x - x0),
+        smooth(position.y - y0),
+        smooth(position.z - z0),
+    );
+
+    let (x0, y0, z0) = (x0 as i32, y0 as i32, z0 as i32);
+
+    let mut value = 0.;
+
+    for (dx, wx) in [(0, 1. - tx), (1, tx)] {
+        for (dy, wy) in [(0, 1. - ty), (1, ty)] {
+            for (dz, wz) in [(0, 1. - tz), (1, tz)] {
+                value += wx * wy * wz * lattice_value(x0 + dx, y0 + dy, z0 + dz, seed);
+            }
+        }
+    }
+
+    value
+}
+
+fn smooth(t: f32) -> f32 {
+    t * t * (3. - 2. * t)
+}
+
+fn lattice_value(x: i32, y: i32, z: i32, seed: u32) -> f32 {
+    let mut hash = seed
+        ^ (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ (z as u32).wrapping_mul(0xcb1a_b31f);
+
+    hash ^= hash >> 16;
+    hash = hash.wrapping_mul(0x7feb_352d);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x846c_a68b);
+    hash ^= hash >> 16;
+
+    hash as f32 / u32::MAX as f32
+}
@@ -10,7 +10,7 @@ pub struct CollectionObject {
 }
 
 impl Object for CollectionObject {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>> {
         if self.objects.is_empty() {
             return None;
         }
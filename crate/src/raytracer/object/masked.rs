@@ -0,0 +1,42 @@
+use serde::Deserialize;
+
+use crate::raytracer::{ray::Ray, texture::ScalarTexture};
+
+use super::{Hit, Object, ObjectKind};
+
+const SKIP_OFFSET: f32 = 0.001;
+
+// NOTE - Applies an opacity mask to another object. Hits where the mask is below the threshold
+// are skipped, so the ray continues on to whatever is behind them. Without a threshold, the
+// opacity is used as the probability of a hit, giving stochastic partial transparency.
+
+#[derive(Clone, Deserialize)]
+pub struct MaskedObject {
+    pub object: Box<ObjectKind>,
+    pub opacity: ScalarTexture,
+    #[serde(default)]
+    pub threshold: Option<f32>,
+}
+
+impl Object for MaskedObject {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>> {
+        let mut t_start = t_min;
+
+        loop {
+            let hit = self.object.hit(ray, t_start, t_max)?;
+
+            let opacity = self.opacity.value(hit.u, hit.v, hit.position);
+
+            let is_opaque = match self.threshold {
+                Some(threshold) => opacity >= threshold,
+                None => opacity > rand::random::<f32>(),
+            };
+
+            if is_opaque {
+                return Some(hit);
+            }
+
+            t_start = hit.t + SKIP_OFFSET;
+        }
+    }
+}
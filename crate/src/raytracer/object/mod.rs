@@ -1,6 +1,9 @@
 use serde::Deserialize;
 
-use self::{collection::CollectionObject, sphere::SphereObject};
+use self::{
+    collection::CollectionObject, masked::MaskedObject, sphere::SphereObject,
+    transform::TransformObject, volume::VolumeObject,
+};
 
 use super::{
     material::MaterialKind,
@@ -9,35 +12,57 @@ use super::{
 };
 
 mod collection;
+mod masked;
 mod sphere;
+mod transform;
+mod volume;
 
 #[derive(Clone, Deserialize)]
 #[serde(tag = "type")]
 pub enum ObjectKind {
     Collection(CollectionObject),
+    Masked(MaskedObject),
     Sphere(SphereObject),
+    Transform(TransformObject),
+    Volume(VolumeObject),
+}
+
+impl ObjectKind {
+    pub fn contains_volume(&self) -> bool {
+        match self {
+            ObjectKind::Collection(x) => x.objects.iter().any(ObjectKind::contains_volume),
+            ObjectKind::Masked(x) => x.object.contains_volume(),
+            ObjectKind::Sphere(_) => false,
+            ObjectKind::Transform(x) => x.object.contains_volume(),
+            ObjectKind::Volume(_) => true,
+        }
+    }
 }
 
 pub trait Object {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit>;
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>>;
 }
 
 impl Object for ObjectKind {
-    fn hit(&self, ray_in: &Ray, t_min: f32, t_max: f32) -> Option<Hit> {
+    fn hit(&self, ray_in: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>> {
         match self {
             ObjectKind::Collection(x) => x.hit(ray_in, t_min, t_max),
+            ObjectKind::Masked(x) => x.hit(ray_in, t_min, t_max),
             ObjectKind::Sphere(x) => x.hit(ray_in, t_min, t_max),
+            ObjectKind::Transform(x) => x.hit(ray_in, t_min, t_max),
+            ObjectKind::Volume(x) => x.hit(ray_in, t_min, t_max),
         }
     }
 }
 
-#[derive(Debug)]
-pub struct Hit {
+#[derive(Clone, Debug)]
+pub struct Hit<'a> {
     pub t: f32,
     pub position: P3,
     pub normal: V3,
+    pub tangent: V3,
     pub is_front: bool,
-    pub material: MaterialKind,
+    pub material: &'a MaterialKind,
     pub u: f32,
     pub v: f32,
 }
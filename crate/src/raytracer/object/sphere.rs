@@ -1,16 +1,16 @@
-use std::f32::consts::PI;
-
 use serde::Deserialize;
 
 use crate::raytracer::{
+    frame::Frame,
     material::MaterialKind,
     ray::Ray,
+    utils::get_spherical_uv,
     v3::{P3, V3},
 };
 
 use super::{Hit, Object};
 
-#[derive(Copy, Clone, Deserialize)]
+#[derive(Clone, Deserialize)]
 pub struct SphereObject {
     pub position_start: P3,
     pub position_finish: P3,
@@ -28,19 +28,10 @@ impl SphereObject {
 
         self.position_start + coefficient * (self.position_finish - self.position_start)
     }
-
-    fn get_uv(position: &P3) -> (f32, f32) {
-        // NOTE - Spherical coordinates.
-
-        let theta = (-position.y).acos();
-        let phi = (-position.z).atan2(position.x) + PI;
-
-        (phi / (2. * PI), theta / PI)
-    }
 }
 
 impl Object for SphereObject {
-    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit> {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>> {
         let position = self.get_position(ray.time);
         let normal = ray.position - position;
 
@@ -74,14 +65,30 @@ impl Object for SphereObject {
             -outward_normal
         };
 
-        let (u, v) = SphereObject::get_uv(&outward_normal);
+        let (u, v) = get_spherical_uv(&outward_normal);
+
+        // NOTE - The tangent follows the direction of increasing u. It is undefined at the
+        // poles, where any perpendicular direction will do.
+
+        let tangent = V3 {
+            x: outward_normal.z,
+            y: 0.,
+            z: -outward_normal.x,
+        };
+
+        let tangent = if tangent.is_near_zero() {
+            Frame::from_normal(outward_normal).tangent
+        } else {
+            tangent.unit()
+        };
 
         Some(Hit {
             t,
             position: hit_position,
             normal,
+            tangent,
             is_front,
-            material: self.material,
+            material: &self.material,
             u,
             v,
         })
@@ -0,0 +1,112 @@
+use std::convert::TryFrom;
+
+use serde::Deserialize;
+
+use crate::raytracer::{animation::Animated, frame::Frame, ray::Ray, v3::V3};
+
+use super::{Hit, Object, ObjectKind};
+
+// NOTE - Moves another object by a translation, a rotation in degrees about the x, y and z axes
+// (applied in that order) and a uniform scale. Each can be animated, and is sampled at the time
+// of the ray.
+
+#[derive(Clone, Deserialize)]
+#[serde(try_from = "TransformObjectSource")]
+pub struct TransformObject {
+    pub object: Box<ObjectKind>,
+    pub translation: Animated<V3>,
+    pub rotation: Animated<V3>,
+    pub scale: Animated<f32>,
+}
+
+#[derive(Deserialize)]
+struct TransformObjectSource {
+    object: Box<ObjectKind>,
+    #[serde(default)]
+    translation: Animated<V3>,
+    #[serde(default)]
+    rotation: Animated<V3>,
+    #[serde(default = "TransformObject::default_scale")]
+    scale: Animated<f32>,
+}
+
+impl TryFrom<TransformObjectSource> for TransformObject {
+    type Error = String;
+
+    fn try_from(source: TransformObjectSource) -> Result<Self, Self::Error> {
+        // NOTE - Volume media are sampled along the world space ray, so a transformed volume
+        // would look up its density in the wrong place.
+
+        if source.object.contains_volume() {
+            return Err("Transform objects cannot contain volumes".to_string());
+        }
+
+        Ok(TransformObject {
+            object: source.object,
+            translation: source.translation,
+            rotation: source.rotation,
+            scale: source.scale,
+        })
+    }
+}
+
+impl TransformObject {
+    fn default_scale() -> Animated<f32> {
+        Animated::Constant(1.)
+    }
+
+    fn get_rotation(&self, time: f32) -> Frame {
+        // NOTE - The columns of the rotation matrix, stored as a frame.
+
+        let angles = self.rotation.at(time);
+
+        let (sx, cx) = angles.x.to_radians().sin_cos();
+        let (sy, cy) = angles.y.to_radians().sin_cos();
+        let (sz, cz) = angles.z.to_radians().sin_cos();
+
+        Frame {
+            tangent: V3 {
+                x: cy * cz,
+                y: cy * sz,
+                z: -sy,
+            },
+            bitangent: V3 {
+                x: sx * sy * cz - cx * sz,
+                y: sx * sy * sz + cx * cz,
+                z: sx * cy,
+            },
+            normal: V3 {
+                x: cx * sy * cz + sx * sz,
+                y: cx * sy * sz - sx * cz,
+                z: cx * cy,
+            },
+        }
+    }
+}
+
+impl Object for TransformObject {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>> {
+        let translation = self.translation.at(ray.time);
+        let rotation = self.get_rotation(ray.time);
+        let scale = self.scale.at(ray.time);
+
+        // NOTE - Transform the ray into object space. Scaling the position and direction alike
+        // leaves the distances along the ray unchanged.
+
+        let local_ray = Ray {
+            position: rotation.to_local(&(ray.position - translation)) / scale,
+            direction: rotation.to_local(&ray.direction) / scale,
+            time: ray.time,
+            wavelength: ray.wavelength,
+        };
+
+        let hit = self.object.hit(&local_ray, t_min, t_max)?;
+
+        Some(Hit {
+            position: ray.at(hit.t),
+            normal: rotation.to_world(&hit.normal),
+            tangent: rotation.to_world(&hit.tangent),
+            ..hit
+        })
+    }
+}
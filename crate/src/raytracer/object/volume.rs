@@ -0,0 +1,151 @@
+use serde::Deserialize;
+
+use crate::raytracer::{
+    color::Color,
+    frame::Frame,
+    material::{InterfaceMaterial, MaterialKind},
+    medium::{DensityGrid, GridMedium, MediumKind},
+    phase_function::PhaseFunctionKind,
+    ray::Ray,
+    v3::{P3, V3},
+};
+
+use super::{Hit, Object};
+
+// NOTE - An axis-aligned box filled with a heterogeneous medium. The box itself is invisible,
+// and the absorption and scattering coefficients are scaled by the density grid. The boundary
+// and its medium are built once, so intersecting the box doesn't allocate.
+
+#[derive(Clone, Deserialize)]
+#[serde(from = "VolumeObjectSource")]
+pub struct VolumeObject {
+    pub minimum: P3,
+    pub maximum: P3,
+    pub boundary: MaterialKind,
+}
+
+#[derive(Deserialize)]
+struct VolumeObjectSource {
+    minimum: P3,
+    maximum: P3,
+    absorption: Color,
+    scattering: Color,
+    density: DensityGrid,
+    #[serde(default)]
+    phase_function: PhaseFunctionKind,
+}
+
+impl From<VolumeObjectSource> for VolumeObject {
+    fn from(source: VolumeObjectSource) -> Self {
+        let medium = MediumKind::Grid(GridMedium {
+            minimum: source.minimum,
+            maximum: source.maximum,
+            absorption: source.absorption,
+            scattering: source.scattering,
+            density: source.density,
+            phase_function: source.phase_function,
+        });
+
+        VolumeObject {
+            minimum: source.minimum,
+            maximum: source.maximum,
+            boundary: MaterialKind::Interface(InterfaceMaterial {
+                medium: Some(medium),
+            }),
+        }
+    }
+}
+
+impl Object for VolumeObject {
+    fn hit(&self, ray: &Ray, t_min: f32, t_max: f32) -> Option<Hit<'_>> {
+        // NOTE - Slab intersection, keeping track of which face is hit on entry and exit.
+
+        let mut t_enter = f32::NEG_INFINITY;
+        let mut t_exit = f32::INFINITY;
+
+        let mut normal_enter = V3::default();
+        let mut normal_exit = V3::default();
+
+        for axis in 0..3 {
+            let (origin, direction, minimum, maximum) = match axis {
+                0 => (
+                    ray.position.x,
+                    ray.direction.x,
+                    self.minimum.x,
+                    self.maximum.x,
+                ),
+                1 => (
+                    ray.position.y,
+                    ray.direction.y,
+                    self.minimum.y,
+                    self.maximum.y,
+                ),
+                _ => (
+                    ray.position.z,
+                    ray.direction.z,
+                    self.minimum.z,
+                    self.maximum.z,
+                ),
+            };
+
+            let mut axis_normal = V3::default();
+
+            match axis {
+                0 => axis_normal.x = 1.,
+                1 => axis_normal.y = 1.,
+                _ => axis_normal.z = 1.,
+            }
+
+            let (t0, t1) = (
+                (minimum - origin) / direction,
+                (maximum - origin) / direction,
+            );
+
+            let (t_near, t_far, normal_near) = if t0 < t1 {
+                (t0, t1, -axis_normal)
+            } else {
+                (t1, t0, axis_normal)
+            };
+
+            if t_near > t_enter {
+                t_enter = t_near;
+                normal_enter = normal_near;
+            }
+
+            if t_far < t_exit {
+                t_exit = t_far;
+                normal_exit = -normal_near;
+            }
+        }
+
+        if t_enter > t_exit {
+            return None;
+        }
+
+        let (t, outward_normal) = if t_enter >= t_min && t_enter <= t_max {
+            (t_enter, normal_enter)
+        } else if t_exit >= t_min && t_exit <= t_max {
+            (t_exit, normal_exit)
+        } else {
+            return None;
+        };
+
+        let is_front = V3::dot(&ray.direction, &outward_normal) < 0.;
+        let tangent = Frame::from_normal(outward_normal).tangent;
+
+        Some(Hit {
+            t,
+            position: ray.at(t),
+            normal: if is_front {
+                outward_normal
+            } else {
+                -outward_normal
+            },
+            tangent,
+            is_front,
+            material: &self.boundary,
+            u: 0.,
+            v: 0.,
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+
+use crate::raytracer::v3::V3;
+
+use super::{henyey_greenstein::HenyeyGreensteinPhaseFunction, PhaseFunction};
+
+// NOTE - A weighted blend of two Henyey-Greenstein lobes, typically one forward and one backward
+// scattering, as seen in clouds and skin.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct DoubleHenyeyGreensteinPhaseFunction {
+    pub g_forward: f32,
+    pub g_backward: f32,
+    pub weight: f32,
+}
+
+impl PhaseFunction for DoubleHenyeyGreensteinPhaseFunction {
+    fn evaluate(&self, direction_in: &V3, direction_out: &V3) -> f32 {
+        let cos_theta = V3::dot(&direction_in.unit(), &direction_out.unit());
+
+        self.weight * HenyeyGreensteinPhaseFunction::evaluate_cosine(self.g_forward, cos_theta)
+            + (1. - self.weight)
+                * HenyeyGreensteinPhaseFunction::evaluate_cosine(self.g_backward, cos_theta)
+    }
+
+    fn sample(&self, direction_in: &V3) -> V3 {
+        let g = if self.weight > rand::random::<f32>() {
+            self.g_forward
+        } else {
+            self.g_backward
+        };
+
+        HenyeyGreensteinPhaseFunction::sample_direction(g, direction_in)
+    }
+}
@@ -0,0 +1,55 @@
+use std::f32::consts::PI;
+
+use serde::Deserialize;
+
+use crate::raytracer::{frame::Frame, v3::V3};
+
+use super::PhaseFunction;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct HenyeyGreensteinPhaseFunction {
+    pub g: f32,
+}
+
+impl HenyeyGreensteinPhaseFunction {
+    pub fn evaluate_cosine(g: f32, cos_theta: f32) -> f32 {
+        let denominator = 1. + g * g - 2. * g * cos_theta;
+
+        (1. - g * g) / (4. * PI * denominator * denominator.sqrt())
+    }
+
+    pub fn sample_direction(g: f32, direction_in: &V3) -> V3 {
+        let xi = rand::random::<f32>();
+
+        let cos_theta = if g.abs() < 1e-3 {
+            1. - 2. * xi
+        } else {
+            let t = (1. - g * g) / (1. - g + 2. * g * xi);
+
+            ((1. + g * g - t * t) / (2. * g)).clamp(-1., 1.)
+        };
+
+        let sin_theta = (1. - cos_theta * cos_theta).max(0.).sqrt();
+        let phi = 2. * PI * rand::random::<f32>();
+
+        let frame = Frame::from_normal(*direction_in);
+
+        frame.to_world(&V3 {
+            x: sin_theta * phi.cos(),
+            y: sin_theta * phi.sin(),
+            z: cos_theta,
+        })
+    }
+}
+
+impl PhaseFunction for HenyeyGreensteinPhaseFunction {
+    fn evaluate(&self, direction_in: &V3, direction_out: &V3) -> f32 {
+        let cos_theta = V3::dot(&direction_in.unit(), &direction_out.unit());
+
+        HenyeyGreensteinPhaseFunction::evaluate_cosine(self.g, cos_theta)
+    }
+
+    fn sample(&self, direction_in: &V3) -> V3 {
+        HenyeyGreensteinPhaseFunction::sample_direction(self.g, direction_in)
+    }
+}
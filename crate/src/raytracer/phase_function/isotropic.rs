@@ -0,0 +1,20 @@
+use std::f32::consts::PI;
+
+use serde::Deserialize;
+
+use crate::raytracer::v3::V3;
+
+use super::PhaseFunction;
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+pub struct IsotropicPhaseFunction {}
+
+impl PhaseFunction for IsotropicPhaseFunction {
+    fn evaluate(&self, _direction_in: &V3, _direction_out: &V3) -> f32 {
+        1. / (4. * PI)
+    }
+
+    fn sample(&self, _direction_in: &V3) -> V3 {
+        V3::random_in_sphere(1.).unit()
+    }
+}
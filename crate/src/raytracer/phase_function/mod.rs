@@ -0,0 +1,160 @@
+use serde::Deserialize;
+
+use self::{
+    double_henyey_greenstein::DoubleHenyeyGreensteinPhaseFunction,
+    henyey_greenstein::HenyeyGreensteinPhaseFunction, isotropic::IsotropicPhaseFunction,
+};
+
+use super::v3::V3;
+
+mod double_henyey_greenstein;
+mod henyey_greenstein;
+mod isotropic;
+
+// NOTE - Phase functions describe the angular distribution of light scattered within a medium.
+// Directions are the direction of travel, so a positive asymmetry favors forward scattering.
+
+#[derive(Clone, Copy, Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum PhaseFunctionKind {
+    DoubleHenyeyGreenstein(DoubleHenyeyGreensteinPhaseFunction),
+    HenyeyGreenstein(HenyeyGreensteinPhaseFunction),
+    Isotropic(IsotropicPhaseFunction),
+}
+
+impl Default for PhaseFunctionKind {
+    fn default() -> Self {
+        PhaseFunctionKind::Isotropic(IsotropicPhaseFunction {})
+    }
+}
+
+pub trait PhaseFunction {
+    fn evaluate(&self, direction_in: &V3, direction_out: &V3) -> f32;
+    fn sample(&self, direction_in: &V3) -> V3;
+}
+
+impl PhaseFunction for PhaseFunctionKind {
+    fn evaluate(&self, direction_in: &V3, direction_out: &V3) -> f32 {
+        match self {
+            PhaseFunctionKind::DoubleHenyeyGreenstein(x) => x.evaluate(direction_in, direction_out),
+            PhaseFunctionKind::HenyeyGreenstein(x) => x.evaluate(direction_in, direction_out),
+            PhaseFunctionKind::Isotropic(x) => x.evaluate(direction_in, direction_out),
+        }
+    }
+
+    fn sample(&self, direction_in: &V3) -> V3 {
+        match self {
+            PhaseFunctionKind::DoubleHenyeyGreenstein(x) => x.sample(direction_in),
+            PhaseFunctionKind::HenyeyGreenstein(x) => x.sample(direction_in),
+            PhaseFunctionKind::Isotropic(x) => x.sample(direction_in),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use crate::raytracer::frame::Frame;
+
+    use super::*;
+
+    const BIN_COUNT: usize = 20;
+    const STEPS_PER_BIN: usize = 200;
+    const SAMPLE_COUNT: usize = 200_000;
+
+    fn phase_functions() -> Vec<PhaseFunctionKind> {
+        vec![
+            PhaseFunctionKind::Isotropic(IsotropicPhaseFunction {}),
+            PhaseFunctionKind::HenyeyGreenstein(HenyeyGreensteinPhaseFunction { g: 0.7 }),
+            PhaseFunctionKind::HenyeyGreenstein(HenyeyGreensteinPhaseFunction { g: -0.3 }),
+            PhaseFunctionKind::DoubleHenyeyGreenstein(DoubleHenyeyGreensteinPhaseFunction {
+                g_forward: 0.8,
+                g_backward: -0.5,
+                weight: 0.6,
+            }),
+        ]
+    }
+
+    fn direction_in() -> V3 {
+        V3 {
+            x: 0.3,
+            y: -0.5,
+            z: 0.8,
+        }
+        .unit()
+    }
+
+    fn bin_probability(phase_function: &PhaseFunctionKind, bin: usize) -> f32 {
+        // NOTE - Integrate over the band of directions whose cosine with the incoming direction
+        // falls in the bin. Phase functions only depend on that cosine.
+
+        let direction_in = direction_in();
+        let frame = Frame::from_normal(direction_in);
+
+        let width = 2. / BIN_COUNT as f32;
+        let step = width / STEPS_PER_BIN as f32;
+
+        (0..STEPS_PER_BIN)
+            .map(|i| {
+                let cos_theta = -1. + bin as f32 * width + (i as f32 + 0.5) * step;
+                let sin_theta = (1. - cos_theta * cos_theta).sqrt();
+
+                let direction_out = frame.to_world(&V3 {
+                    x: sin_theta,
+                    y: 0.,
+                    z: cos_theta,
+                });
+
+                2. * PI * phase_function.evaluate(&direction_in, &direction_out) * step
+            })
+            .sum()
+    }
+
+    #[test]
+    fn evaluate_is_normalized() {
+        for phase_function in phase_functions() {
+            let total: f32 = (0..BIN_COUNT)
+                .map(|bin| bin_probability(&phase_function, bin))
+                .sum();
+
+            assert!(
+                (total - 1.).abs() < 1e-2,
+                "{:?} integrates to {}",
+                phase_function,
+                total
+            );
+        }
+    }
+
+    #[test]
+    fn sample_matches_evaluate() {
+        let direction_in = direction_in();
+
+        for phase_function in phase_functions() {
+            let mut counts = [0; BIN_COUNT];
+
+            for _ in 0..SAMPLE_COUNT {
+                let direction_out = phase_function.sample(&direction_in);
+                let cos_theta = V3::dot(&direction_in, &direction_out.unit());
+
+                let bin = (((cos_theta + 1.) / 2. * BIN_COUNT as f32) as usize).min(BIN_COUNT - 1);
+                counts[bin] += 1;
+            }
+
+            for (bin, &count) in counts.iter().enumerate() {
+                let sampled = count as f32 / SAMPLE_COUNT as f32;
+                let expected = bin_probability(&phase_function, bin);
+
+                assert!(
+                    (sampled - expected).abs() < 1e-2,
+                    "{:?} bin {} sampled {} but expected {}",
+                    phase_function,
+                    bin,
+                    sampled,
+                    expected
+                );
+            }
+        }
+    }
+}
@@ -4,6 +4,7 @@ pub struct Ray {
     pub position: P3,
     pub direction: V3,
     pub time: f32,
+    pub wavelength: Option<f32>,
 }
 
 impl Ray {
@@ -1,6 +1,42 @@
+use std::convert::TryFrom;
+
 use serde::Deserialize;
 
-use super::{camera::Camera, color::Color, object::ObjectKind};
+use super::{background::BackgroundKind, camera::Camera, object::ObjectKind};
+
+// NOTE - Frames are spaced evenly in time, starting at zero. The camera's shutter interval is
+// relative to the start of each frame.
+
+#[derive(Copy, Clone, Deserialize)]
+#[serde(try_from = "AnimationSource")]
+pub struct Animation {
+    pub fps: f32,
+    pub frame_count: u32,
+}
+
+#[derive(Deserialize)]
+struct AnimationSource {
+    fps: f32,
+    frame_count: u32,
+}
+
+impl TryFrom<AnimationSource> for Animation {
+    type Error = String;
+
+    fn try_from(source: AnimationSource) -> Result<Self, Self::Error> {
+        if !(source.fps > 0. && source.fps.is_finite()) {
+            return Err(format!(
+                "Animation fps must be positive, but was {}",
+                source.fps
+            ));
+        }
+
+        Ok(Animation {
+            fps: source.fps,
+            frame_count: source.frame_count,
+        })
+    }
+}
 
 #[derive(Clone, Deserialize)]
 pub struct Scene {
@@ -8,7 +44,46 @@ pub struct Scene {
     pub height: u32,
     pub samples_per_pixel: u32,
     pub max_depth: u32,
-    pub background_color: Color,
+    #[serde(default = "Scene::default_max_volume_depth")]
+    pub max_volume_depth: u32,
+    #[serde(default)]
+    pub russian_roulette_depth: Option<u32>,
+    #[serde(default)]
+    pub spectral: bool,
+    pub background: BackgroundKind,
+    #[serde(default)]
+    pub camera_background: Option<BackgroundKind>,
     pub camera: Camera,
     pub root_object: ObjectKind,
+    #[serde(default)]
+    pub animation: Option<Animation>,
+}
+
+impl Scene {
+    // NOTE - Scattering inside media has its own, larger budget, since dense media such as
+    // subsurface materials need many short steps to carry light back out.
+
+    fn default_max_volume_depth() -> u32 {
+        256
+    }
+
+    pub fn frame_count(&self) -> u32 {
+        match self.animation {
+            Some(animation) => animation.frame_count,
+            None => 1,
+        }
+    }
+
+    pub fn at_frame(&self, frame: u32) -> Scene {
+        let mut scene = self.clone();
+
+        if let Some(animation) = self.animation {
+            let frame_time = frame as f32 / animation.fps;
+
+            scene.camera.time_start += frame_time;
+            scene.camera.time_finish += frame_time;
+        }
+
+        scene
+    }
 }
@@ -0,0 +1,204 @@
+use std::{convert::TryFrom, sync::Arc};
+
+use serde::Deserialize;
+
+// NOTE - How far the shutter is open over the exposure, which weights the times sampled for
+// motion blur. Trapezoid durations are fractions of the exposure spent opening and closing, and
+// custom curves are evenly spaced samples joined linearly.
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(tag = "type")]
+pub enum ShutterCurve {
+    #[default]
+    Box,
+    Trapezoid {
+        open_duration: f32,
+        close_duration: f32,
+    },
+    Custom(CustomShutterCurve),
+}
+
+impl ShutterCurve {
+    pub fn sample(&self) -> f32 {
+        // NOTE - Returns a time as a fraction of the exposure, distributed like the curve.
+
+        match self {
+            ShutterCurve::Box => rand::random::<f32>(),
+            ShutterCurve::Trapezoid {
+                open_duration,
+                close_duration,
+            } => sample_trapezoid(*open_duration, *close_duration),
+            ShutterCurve::Custom(curve) => curve.sample(),
+        }
+    }
+}
+
+fn sample_trapezoid(open_duration: f32, close_duration: f32) -> f32 {
+    let open_duration = open_duration.clamp(0., 1.);
+    let close_duration = close_duration.clamp(0., 1. - open_duration);
+
+    // NOTE - Choose the opening ramp, the fully open section or the closing ramp by area, then
+    // sample within it.
+
+    let open_area = open_duration / 2.;
+    let full_area = 1. - open_duration - close_duration;
+    let close_area = close_duration / 2.;
+
+    let x = rand::random::<f32>() * (open_area + full_area + close_area);
+    let xi = rand::random::<f32>();
+
+    if x < open_area {
+        open_duration * xi.sqrt()
+    } else if x < open_area + full_area {
+        open_duration + xi * full_area
+    } else {
+        1. - close_duration * xi.sqrt()
+    }
+}
+
+// NOTE - The cumulative areas under each linear segment are computed when the curve is loaded,
+// so a time can be sampled by choosing a segment and inverting its integral.
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(try_from = "CustomShutterCurveSource")]
+pub struct CustomShutterCurve {
+    values: Arc<Vec<f32>>,
+    cdf: Arc<Vec<f32>>,
+}
+
+#[derive(Deserialize)]
+struct CustomShutterCurveSource {
+    values: Vec<f32>,
+}
+
+impl TryFrom<CustomShutterCurveSource> for CustomShutterCurve {
+    type Error = String;
+
+    fn try_from(source: CustomShutterCurveSource) -> Result<Self, Self::Error> {
+        let values = source.values;
+
+        if values.len() < 2 {
+            return Err(format!(
+                "Custom shutter curves need at least two values, but found {}",
+                values.len()
+            ));
+        }
+
+        if let Some(value) = values
+            .iter()
+            .find(|value| !(**value >= 0. && value.is_finite()))
+        {
+            return Err(format!(
+                "Custom shutter curve values must be non-negative, but found {}",
+                value
+            ));
+        }
+
+        let mut total = 0.;
+
+        let mut cdf: Vec<f32> = values
+            .windows(2)
+            .map(|pair| {
+                total += (pair[0] + pair[1]) / 2.;
+                total
+            })
+            .collect();
+
+        if total <= 0. {
+            return Err("Custom shutter curves must open at some point".to_string());
+        }
+
+        for value in cdf.iter_mut() {
+            *value /= total;
+        }
+
+        Ok(CustomShutterCurve {
+            values: Arc::new(values),
+            cdf: Arc::new(cdf),
+        })
+    }
+}
+
+impl CustomShutterCurve {
+    fn sample(&self) -> f32 {
+        // NOTE - Segments that stay closed don't raise the distribution, so they are never
+        // chosen.
+
+        let target = rand::random::<f32>();
+        let index = self
+            .cdf
+            .partition_point(|value| *value <= target)
+            .min(self.cdf.len() - 1);
+
+        let fraction = invert_segment(
+            self.values[index],
+            self.values[index + 1],
+            rand::random::<f32>(),
+        );
+
+        ((index as f32 + fraction) / self.cdf.len() as f32).clamp(0., 1.)
+    }
+}
+
+fn invert_segment(start: f32, finish: f32, xi: f32) -> f32 {
+    // NOTE - Over the segment the curve is start + (finish - start) x, so its integral up to x
+    // is start x + (finish - start) x² / 2. Solving for the fraction xi of the total area, in
+    // the form that stays stable when the slope is zero.
+
+    let target = xi * (start + finish) / 2.;
+    let denominator = start
+        + (start * start + 2. * (finish - start) * target)
+            .max(0.)
+            .sqrt();
+
+    if denominator <= 0. {
+        return 0.;
+    }
+
+    (2. * target / denominator).clamp(0., 1.)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(values: Vec<f32>) -> Result<CustomShutterCurve, String> {
+        CustomShutterCurve::try_from(CustomShutterCurveSource { values })
+    }
+
+    #[test]
+    fn custom_curve_rejects_invalid_values() {
+        assert!(curve(vec![]).is_err());
+        assert!(curve(vec![1.]).is_err());
+        assert!(curve(vec![1., -1.]).is_err());
+        assert!(curve(vec![1., f32::INFINITY]).is_err());
+        assert!(curve(vec![0., 0., 0.]).is_err());
+    }
+
+    #[test]
+    fn invert_segment_inverts_the_integral() {
+        for (start, finish) in [(1., 1.), (0., 1.), (1., 0.), (0.25, 2.)] {
+            for xi in [0., 0.1, 0.5, 0.9, 1.] {
+                let x = invert_segment(start, finish, xi);
+                let area = start * x + (finish - start) * x * x / 2.;
+
+                assert!(
+                    (area - xi * (start + finish) / 2.).abs() < 1e-5,
+                    "start {} finish {} xi {}",
+                    start,
+                    finish,
+                    xi
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn custom_curve_skips_closed_segments() {
+        let curve = curve(vec![0., 0., 1., 1.]).unwrap();
+
+        for _ in 0..1000 {
+            assert!(curve.sample() >= 1. / 3.);
+        }
+    }
+}
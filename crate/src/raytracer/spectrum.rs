@@ -0,0 +1,308 @@
+use std::ops;
+
+use super::color::Color;
+
+pub const WAVELENGTH_COUNT: usize = 4;
+
+const WAVELENGTH_MINIMUM: f32 = 380.;
+const WAVELENGTH_MAXIMUM: f32 = 720.;
+
+// NOTE - Representative wavelengths of the red, green and blue channels in RGB mode.
+
+const RGB_WAVELENGTHS: [f32; 3] = [650., 550., 450.];
+
+// NOTE - Integral of the CIE Y color matching function over the sampled range, and the linear
+// sRGB color of the equal-energy spectrum. These normalize the output so that a constant
+// spectrum of 1 maps to white.
+
+const CIE_Y_INTEGRAL: f32 = 106.911_87;
+const EQUAL_ENERGY_WHITE: Color = Color {
+    r: 1.200_606,
+    g: 0.949_638,
+    b: 0.907_908,
+};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Spectrum {
+    pub values: [f32; WAVELENGTH_COUNT],
+}
+
+impl Spectrum {
+    pub fn constant(value: f32) -> Spectrum {
+        Spectrum {
+            values: [value; WAVELENGTH_COUNT],
+        }
+    }
+
+    pub fn max_component(&self) -> f32 {
+        self.values.iter().cloned().fold(f32::MIN, f32::max)
+    }
+}
+
+impl ops::AddAssign<Spectrum> for Spectrum {
+    fn add_assign(&mut self, rhs: Spectrum) {
+        for (value, rhs_value) in self.values.iter_mut().zip(rhs.values.iter()) {
+            *value += rhs_value;
+        }
+    }
+}
+
+impl ops::Add<Spectrum> for Spectrum {
+    type Output = Spectrum;
+
+    fn add(self, rhs: Spectrum) -> Spectrum {
+        let mut values = self.values;
+
+        for (value, rhs_value) in values.iter_mut().zip(rhs.values.iter()) {
+            *value += rhs_value;
+        }
+
+        Spectrum { values }
+    }
+}
+
+impl ops::Sub<Spectrum> for Spectrum {
+    type Output = Spectrum;
+
+    fn sub(self, rhs: Spectrum) -> Spectrum {
+        let mut values = self.values;
+
+        for (value, rhs_value) in values.iter_mut().zip(rhs.values.iter()) {
+            *value -= rhs_value;
+        }
+
+        Spectrum { values }
+    }
+}
+
+impl ops::Mul<Spectrum> for Spectrum {
+    type Output = Spectrum;
+
+    fn mul(self, rhs: Spectrum) -> Spectrum {
+        let mut values = self.values;
+
+        for (value, rhs_value) in values.iter_mut().zip(rhs.values.iter()) {
+            *value *= rhs_value;
+        }
+
+        Spectrum { values }
+    }
+}
+
+impl ops::Mul<f32> for Spectrum {
+    type Output = Spectrum;
+
+    fn mul(self, rhs: f32) -> Spectrum {
+        let mut values = self.values;
+
+        for value in values.iter_mut() {
+            *value *= rhs;
+        }
+
+        Spectrum { values }
+    }
+}
+
+impl ops::Div<f32> for Spectrum {
+    type Output = Spectrum;
+
+    fn div(self, rhs: f32) -> Spectrum {
+        let mut values = self.values;
+
+        for value in values.iter_mut() {
+            *value /= rhs;
+        }
+
+        Spectrum { values }
+    }
+}
+
+// NOTE - The wavelengths carried by a path. In RGB mode the first three spectrum values are
+// simply the red, green and blue channels. In spectral mode they are radiance at a hero
+// wavelength and evenly spaced rotations of it (Wilkie et al. 2014).
+
+#[derive(Clone, Debug)]
+pub enum Wavelengths {
+    Rgb,
+    Sampled([f32; WAVELENGTH_COUNT]),
+}
+
+impl Wavelengths {
+    pub fn new(is_spectral: bool) -> Wavelengths {
+        if !is_spectral {
+            return Wavelengths::Rgb;
+        }
+
+        let range = WAVELENGTH_MAXIMUM - WAVELENGTH_MINIMUM;
+        let hero = rand::random::<f32>() * range;
+
+        let mut wavelengths = [0.; WAVELENGTH_COUNT];
+
+        for (i, wavelength) in wavelengths.iter_mut().enumerate() {
+            let offset = (hero + i as f32 * range / WAVELENGTH_COUNT as f32) % range;
+
+            *wavelength = WAVELENGTH_MINIMUM + offset;
+        }
+
+        Wavelengths::Sampled(wavelengths)
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            Wavelengths::Rgb => 3,
+            Wavelengths::Sampled(_) => WAVELENGTH_COUNT,
+        }
+    }
+
+    pub fn hero(&self) -> Option<f32> {
+        match self {
+            Wavelengths::Rgb => None,
+            Wavelengths::Sampled(wavelengths) => Some(wavelengths[0]),
+        }
+    }
+
+    pub fn upsample(&self, color: Color) -> Spectrum {
+        match self {
+            Wavelengths::Rgb => Spectrum {
+                values: [color.r, color.g, color.b, 0.],
+            },
+            Wavelengths::Sampled(wavelengths) => {
+                let mut values = [0.; WAVELENGTH_COUNT];
+
+                for (value, &wavelength) in values.iter_mut().zip(wavelengths.iter()) {
+                    *value = upsample_rgb(color, wavelength);
+                }
+
+                Spectrum { values }
+            }
+        }
+    }
+
+    pub fn terminate_secondary(&self, throughput: Spectrum) -> Spectrum {
+        // NOTE - Wavelength-dependent scattering (e.g. dispersion) sends each wavelength in a
+        // different direction, so only the hero wavelength can continue. It is re-weighted to
+        // keep the estimate unbiased.
+
+        match self {
+            Wavelengths::Rgb => throughput,
+            Wavelengths::Sampled(_) => {
+                let mut values = [0.; WAVELENGTH_COUNT];
+                values[0] = WAVELENGTH_COUNT as f32 * throughput.values[0];
+
+                Spectrum { values }
+            }
+        }
+    }
+
+    pub fn select_single(&self) -> (f32, Spectrum) {
+        // NOTE - Picks one wavelength to trace on its own, along with a throughput that keeps
+        // only it. In RGB mode, a channel is chosen at random.
+
+        match self {
+            Wavelengths::Rgb => {
+                let channel = ((rand::random::<f32>() * 3.) as usize).min(2);
+
+                let mut values = [0.; WAVELENGTH_COUNT];
+                values[channel] = 3.;
+
+                (RGB_WAVELENGTHS[channel], Spectrum { values })
+            }
+            Wavelengths::Sampled(wavelengths) => (
+                wavelengths[0],
+                self.terminate_secondary(Spectrum::constant(1.)),
+            ),
+        }
+    }
+
+    pub fn to_color(&self, spectrum: Spectrum) -> Color {
+        match self {
+            Wavelengths::Rgb => Color {
+                r: spectrum.values[0],
+                g: spectrum.values[1],
+                b: spectrum.values[2],
+            },
+            Wavelengths::Sampled(wavelengths) => {
+                let (mut x, mut y, mut z) = (0., 0., 0.);
+
+                for (&value, &wavelength) in spectrum.values.iter().zip(wavelengths.iter()) {
+                    x += value * cie_x(wavelength);
+                    y += value * cie_y(wavelength);
+                    z += value * cie_z(wavelength);
+                }
+
+                let scale = (WAVELENGTH_MAXIMUM - WAVELENGTH_MINIMUM)
+                    / (WAVELENGTH_COUNT as f32 * CIE_Y_INTEGRAL);
+
+                let (x, y, z) = (scale * x, scale * y, scale * z);
+
+                Color {
+                    r: (3.240_454 * x - 1.537_138 * y - 0.498_531 * z) / EQUAL_ENERGY_WHITE.r,
+                    g: (-0.969_266 * x + 1.876_011 * y + 0.041_556 * z) / EQUAL_ENERGY_WHITE.g,
+                    b: (0.055_643 * x - 0.204_026 * y + 1.057_225 * z) / EQUAL_ENERGY_WHITE.b,
+                }
+            }
+        }
+    }
+}
+
+pub fn evaluate_per_wavelength<F>(wavelength: Option<f32>, f: F) -> Color
+where
+    F: Fn(f32, &dyn Fn(Color) -> f32) -> f32,
+{
+    // NOTE - Evaluates a wavelength-dependent quantity as a color. In RGB mode, each channel is
+    // evaluated at a representative wavelength. In spectral mode, only the hero wavelength is
+    // evaluated. The callback receives a helper to sample RGB inputs at the given wavelength.
+
+    match wavelength {
+        Some(wavelength) => Color::gray(f(wavelength, &|color| upsample_rgb(color, wavelength))),
+        None => Color {
+            r: f(RGB_WAVELENGTHS[0], &|color| color.r),
+            g: f(RGB_WAVELENGTHS[1], &|color| color.g),
+            b: f(RGB_WAVELENGTHS[2], &|color| color.b),
+        },
+    }
+}
+
+fn upsample_rgb(color: Color, wavelength: f32) -> f32 {
+    // NOTE - Smooth blue, green and red basis functions that sum to one, so that gray colors
+    // upsample to flat spectra.
+
+    let blue_to_green = smoothstep(480., 520., wavelength);
+    let green_to_red = smoothstep(570., 610., wavelength);
+
+    color.b * (1. - blue_to_green)
+        + color.g * (blue_to_green - green_to_red)
+        + color.r * green_to_red
+}
+
+fn smoothstep(edge_start: f32, edge_finish: f32, x: f32) -> f32 {
+    let t = ((x - edge_start) / (edge_finish - edge_start)).clamp(0., 1.);
+
+    t * t * (3. - 2. * t)
+}
+
+// NOTE - Multi-lobe Gaussian fit of the CIE 1931 color matching functions from Wyman, Sloan and
+// Shirley (2013).
+
+fn gaussian(x: f32, mean: f32, sigma_low: f32, sigma_high: f32) -> f32 {
+    let sigma = if x < mean { sigma_low } else { sigma_high };
+    let t = (x - mean) / sigma;
+
+    (-0.5 * t * t).exp()
+}
+
+fn cie_x(wavelength: f32) -> f32 {
+    1.056 * gaussian(wavelength, 599.8, 37.9, 31.0)
+        + 0.362 * gaussian(wavelength, 442.0, 16.0, 26.7)
+        - 0.065 * gaussian(wavelength, 501.1, 20.4, 26.2)
+}
+
+fn cie_y(wavelength: f32) -> f32 {
+    0.821 * gaussian(wavelength, 568.8, 46.9, 40.5)
+        + 0.286 * gaussian(wavelength, 530.9, 16.3, 31.1)
+}
+
+fn cie_z(wavelength: f32) -> f32 {
+    1.217 * gaussian(wavelength, 437.0, 11.8, 36.0)
+        + 0.681 * gaussian(wavelength, 459.0, 26.0, 13.8)
+}
@@ -4,8 +4,11 @@ use self::solid::SolidTexture;
 
 use super::{color::Color, v3::P3};
 
+mod scalar;
 mod solid;
 
+pub use scalar::ScalarTexture;
+
 #[derive(Copy, Clone, Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum TextureKind {
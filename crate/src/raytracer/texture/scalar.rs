@@ -0,0 +1,29 @@
+use serde::Deserialize;
+
+use crate::raytracer::v3::P3;
+
+use super::{Texture, TextureKind};
+
+// NOTE - A scalar material input that is either a constant or the luminance of a texture.
+
+#[derive(Copy, Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ScalarTexture {
+    Constant(f32),
+    Texture(TextureKind),
+}
+
+impl ScalarTexture {
+    pub fn value(&self, u: f32, v: f32, position: P3) -> f32 {
+        match self {
+            ScalarTexture::Constant(x) => *x,
+            ScalarTexture::Texture(t) => t.value(u, v, position).luminance(),
+        }
+    }
+}
+
+impl Default for ScalarTexture {
+    fn default() -> Self {
+        ScalarTexture::Constant(0.)
+    }
+}
@@ -1,5 +1,18 @@
+use std::f32::consts::PI;
+
 use rand::random;
 
+use super::v3::V3;
+
 pub fn random_in_range(minimum: f32, maximum: f32) -> f32 {
     minimum + random::<f32>() * (maximum - minimum)
 }
+
+pub fn get_spherical_uv(direction: &V3) -> (f32, f32) {
+    // NOTE - Spherical coordinates of a unit direction.
+
+    let theta = (-direction.y).acos();
+    let phi = (-direction.z).atan2(direction.x) + PI;
+
+    (phi / (2. * PI), theta / PI)
+}
@@ -29,15 +29,40 @@ const progressEl = document.getElementById("progress") as HTMLDivElement;
 
 const outputEl = document.getElementById("output") as HTMLCanvasElement;
 
+// NOTE - Every rendered frame is kept, so animations can be downloaded in full.
+
+let frames: ImageData[] = [];
+
 const downloadEl = document.getElementById("download") as HTMLButtonElement;
 downloadEl.onclick = function () {
+  if (frames.length <= 1) {
+    downloadImage(outputEl, "fermion_out.png");
+    return;
+  }
+
+  const canvas = document.createElement("canvas");
+  canvas.width = outputEl.width;
+  canvas.height = outputEl.height;
+
+  const ctx = canvas.getContext("2d") as CanvasRenderingContext2D;
+
+  frames.forEach((frame, index) => {
+    ctx.putImageData(frame, 0, 0);
+    downloadImage(
+      canvas,
+      `fermion_out_${String(index).padStart(4, "0")}.png`
+    );
+  });
+};
+
+function downloadImage(canvas: HTMLCanvasElement, name: string) {
   const link = document.createElement("a");
 
-  link.download = "fermion_out.png";
-  link.href = outputEl.toDataURL();
+  link.download = name;
+  link.href = canvas.toDataURL();
 
   link.click();
-};
+}
 
 (async function initializeWasm() {
   const wasm = Comlink.wrap<IWASM>(
@@ -69,11 +94,33 @@ downloadEl.onclick = function () {
     startEl.disabled = true;
     concurrencyInputEl.disabled = true;
 
-    const renderContext = await wasm.render(input, concurrency);
-    new RenderState(renderContext).start();
+    frames = [];
+
+    for await (const frame of renderFrames(wasm, input, concurrency)) {
+      frames.push(frame);
+    }
+
+    concurrencyInputEl.disabled = false;
+    startEl.disabled = false;
   };
 })();
 
+// NOTE - Animated scenes render their frames one after another, yielding each once it is
+// finished.
+
+async function* renderFrames(
+  wasm: Comlink.Remote<IWASM>,
+  input: any,
+  concurrency: number
+): AsyncGenerator<ImageData> {
+  const frameCount = await wasm.frameCount(input);
+
+  for (let frame = 0; frame < frameCount; frame++) {
+    const renderContext = await wasm.renderFrame(input, frame, concurrency);
+    yield await new RenderState(renderContext).start();
+  }
+}
+
 class RenderState {
   private readonly context: any;
 
@@ -84,7 +131,7 @@ class RenderState {
     this.context = context;
   }
 
-  start() {
+  start(): Promise<ImageData> {
     this.startTime = Date.now();
 
     this.intervalId = setInterval(
@@ -92,14 +139,13 @@ class RenderState {
       UPDATE_INTERVAL
     ) as any;
 
-    this.context.getPromise().then((imageData: ImageData) => {
+    return this.context.getPromise().then((imageData: ImageData) => {
       clearInterval(this.intervalId);
 
       this.updateProgress(1);
       this.updateOutput(imageData);
 
-      concurrencyInputEl.disabled = false;
-      startEl.disabled = false;
+      return imageData;
     });
   }
 
@@ -5,6 +5,8 @@ import init, { initThreadPool, Scene } from "../crate/pkg/fermion-wasm";
 export interface IWASM {
   initialize: typeof initialize;
   render: typeof render;
+  frameCount: typeof frameCount;
+  renderFrame: typeof renderFrame;
 }
 
 async function initialize() {
@@ -19,7 +21,20 @@ function render(input: any, concurrency: number) {
   return Comlink.proxy(renderContext);
 }
 
+function frameCount(input: any) {
+  return new Scene(input).frameCount();
+}
+
+function renderFrame(input: any, frame: number, concurrency: number) {
+  const scene = new Scene(input);
+  const renderContext = scene.renderFrame(frame, concurrency);
+
+  return Comlink.proxy(renderContext);
+}
+
 Comlink.expose({
   initialize,
   render,
+  frameCount,
+  renderFrame,
 });